@@ -0,0 +1,111 @@
+//! Chromatic adaptation between illuminants, Bradford transform
+
+use adjust::clip;
+use space::mul3;
+use Color;
+
+/// Standard illuminant, or white point, given by CIE 1931 xy chromaticity
+#[derive(Debug,Copy,Clone,PartialEq)]
+pub enum Illuminant {
+    /// Incandescent, tungsten, about 2856 K
+    A,
+    /// Horizon daylight, 5003 K, used for print
+    D50,
+    /// Mid-morning daylight, 5503 K
+    D55,
+    /// Noon daylight, 6504 K, the sRGB white point
+    D65,
+    /// North sky daylight, 7504 K
+    D75,
+    /// Equal energy
+    E,
+    /// Cool white fluorescent
+    F2,
+    /// Broadband daylight fluorescent
+    F7,
+    /// Narrow band white fluorescent
+    F11,
+    /// Custom white point from xy chromaticity
+    Xy(f64, f64),
+}
+
+impl Illuminant {
+    /// CIE 1931 xy chromaticity, 2 degree observer
+    pub fn xy(&self) -> (f64, f64) {
+        match *self {
+            Illuminant::A => (0.44757, 0.40745),
+            Illuminant::D50 => (0.34567, 0.35850),
+            Illuminant::D55 => (0.33242, 0.34743),
+            Illuminant::D65 => (0.31271, 0.32902),
+            Illuminant::D75 => (0.29902, 0.31485),
+            Illuminant::E => (1.0 / 3.0, 1.0 / 3.0),
+            Illuminant::F2 => (0.37208, 0.37529),
+            Illuminant::F7 => (0.31292, 0.32933),
+            Illuminant::F11 => (0.38052, 0.37713),
+            Illuminant::Xy(x, y) => (x, y),
+        }
+    }
+    /// White point in XYZ with Y = 1
+    fn xyz(&self) -> (f64, f64, f64) {
+        let (x, y) = self.xy();
+        (x / y, 1.0, (1.0 - x - y) / y)
+    }
+}
+
+const BRADFORD: [[f64;3];3] = [
+    [ 0.8951,  0.2664, -0.1614],
+    [-0.7502,  1.7135,  0.0367],
+    [ 0.0389, -0.0685,  1.0296],
+];
+const BRADFORD_INV: [[f64;3];3] = [
+    [ 0.9869929, -0.1470543,  0.1599627],
+    [ 0.4323053,  0.5183603,  0.0492912],
+    [-0.0085287,  0.0400428,  0.9684867],
+];
+
+/// Adapt XYZ from one white point to another
+fn bradford(xyz: (f64,f64,f64), from: &Illuminant, to: &Illuminant) -> (f64,f64,f64) {
+    let s = mul3(&BRADFORD, from.xyz());
+    let d = mul3(&BRADFORD, to.xyz());
+    let (r, g, b) = mul3(&BRADFORD, xyz);
+    mul3(&BRADFORD_INV, (r * d.0 / s.0, g * d.1 / s.1, b * d.2 / s.2))
+}
+
+impl Color {
+    /// Re-balance the Color from one illuminant to another
+    ///
+    ///   A color that appears white under the source illuminant maps
+    ///   to white under the destination.  For example, to correct a
+    ///   photo taken under tungsten light, use from A to D65.  The
+    ///   result is clipped to [0,1] and alpha is unchanged
+    ///
+    /// ```
+    /// # use tint::{Color, Illuminant};
+    /// let gray = Color::from_rgb255(128, 128, 128);
+    /// let warm = gray.white_balance(Illuminant::D65, Illuminant::A);
+    /// assert!(warm.red > warm.blue);
+    /// let back = warm.white_balance(Illuminant::A, Illuminant::D65);
+    /// assert!(back.approx_eq(&gray, 1e-6));
+    /// ```
+    pub fn white_balance(&self, from: Illuminant, to: Illuminant) -> Color {
+        let (x, y, z) = bradford(self.to_xyz(), &from, &to);
+        clip(Color::from_xyz(x, y, z), self.alpha)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn bradford_d65_d50() {
+        // Matches the fixed D65 to D50 adaptation used for Lab
+        let c = Color::new(0.2, 0.4, 0.6, 0.5);
+        let xyz = bradford(c.to_xyz(), &Illuminant::D65, &Illuminant::D50);
+        let (l, a, b) = c.to_lab();
+        let lab = Color::from_color_space(::ColorSpace::XyzD50, xyz.0, xyz.1, xyz.2).to_lab();
+        assert!((lab.0 - l).abs() < 0.05 && (lab.1 - a).abs() < 0.05 && (lab.2 - b).abs() < 0.05);
+        assert!(c.white_balance(Illuminant::D65, Illuminant::D65).approx_eq(&c, 1e-6));
+        assert_eq!(c.white_balance(Illuminant::A, Illuminant::D65).alpha, 0.5);
+        assert_eq!(Illuminant::Xy(0.3, 0.3).xy(), (0.3, 0.3));
+    }
+}
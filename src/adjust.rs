@@ -0,0 +1,264 @@
+//! Color adjustments: lightness, saturation, hue, inversion
+
+use Color;
+
+/// Method used by Color::to_grayscale()
+///
+/// ```
+/// # use tint::{Color, GrayscaleMethod};
+/// let red = Color::from_rgb255(255, 0, 0);
+/// assert_eq!(red.to_grayscale(GrayscaleMethod::Average).to_rgb255(), (85, 85, 85));
+/// ```
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Hash,Default)]
+pub enum GrayscaleMethod {
+    /// Weighted sum with Rec. 709 / sRGB luma coefficients, the default
+    #[default]
+    Rec709,
+    /// Weighted sum with Rec. 601 (NTSC) luma coefficients
+    Rec601,
+    /// Mean of red, green and blue
+    Average,
+    /// Gray with the same CIE Lab lightness
+    Lightness,
+    /// Midpoint of the largest and smallest channel, HSL lightness
+    Desaturation,
+}
+
+impl Color {
+    /// Increase HSL lightness by amount [0,1], clipped to [0,1]
+    ///
+    ///   Alpha is unchanged
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let red = Color::from_rgb255(255, 0, 0);
+    /// assert_eq!(red.lighten(0.25).to_rgb255(), (255, 127, 127));
+    /// ```
+    pub fn lighten(&self, amount: f64) -> Color {
+        let (_,_,l) = self.to_hsl();
+        self.with_lightness(l + amount)
+    }
+    /// Decrease HSL lightness by amount [0,1], clipped to [0,1]
+    ///
+    ///   Alpha is unchanged
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let red = Color::from_rgb255(255, 0, 0);
+    /// assert_eq!(red.darken(0.25).to_rgb255(), (127, 0, 0));
+    /// ```
+    pub fn darken(&self, amount: f64) -> Color {
+        self.lighten(-amount)
+    }
+    /// Increase CIE Lab lightness by amount [0,1], a fraction of the full L range
+    ///
+    ///   Lab lightness is perceptually uniform, so equal amounts give
+    ///   similar visual changes across hues.  Alpha is unchanged and
+    ///   the result is clipped to the sRGB gamut
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let slate = Color::from_rgb255(70, 100, 150);
+    /// let (l0, _, _) = slate.to_lab();
+    /// let (l1, _, _) = slate.lighten_lab(0.1).to_lab();
+    /// assert!((l1 - l0 - 10.0).abs() < 1e-6);
+    /// ```
+    pub fn lighten_lab(&self, amount: f64) -> Color {
+        let (l,a,b) = self.to_lab();
+        clip(Color::from_lab((l + amount * 100.0).clamp(0.0, 100.0), a, b), self.alpha)
+    }
+    /// Decrease CIE Lab lightness by amount [0,1], see lighten_lab()
+    pub fn darken_lab(&self, amount: f64) -> Color {
+        self.lighten_lab(-amount)
+    }
+    /// Increase HSL saturation by amount [0,1], clipped to [0,1]
+    ///
+    ///   Alpha is unchanged
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let c = Color::from_hsl(0.0, 0.5, 0.5);
+    /// assert_eq!(c.saturate(0.5).to_hex(), "#ff0000");
+    /// ```
+    pub fn saturate(&self, amount: f64) -> Color {
+        let (_,s,_) = self.to_hsl();
+        self.with_saturation(s + amount)
+    }
+    /// Decrease HSL saturation by amount [0,1], clipped to [0,1]
+    ///
+    ///   Alpha is unchanged
+    pub fn desaturate(&self, amount: f64) -> Color {
+        self.saturate(-amount)
+    }
+    /// Fully desaturate the Color, keeping HSL lightness
+    ///
+    ///   Alpha is unchanged
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let red = Color::from_rgb255(255, 0, 0);
+    /// assert_eq!(red.grayscale().to_rgb255(), (127, 127, 127));
+    /// ```
+    pub fn grayscale(&self) -> Color {
+        self.with_saturation(0.0)
+    }
+    /// Rotate the hue by degrees, wrapping around the color wheel
+    ///
+    ///   Negative values rotate backwards.  Alpha is unchanged
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let red = Color::from_rgb255(255, 0, 0);
+    /// assert_eq!(red.rotate_hue(120.0).to_hex(), "#00ff00");
+    /// assert_eq!(red.rotate_hue(-120.0).to_hex(), "#0000ff");
+    /// ```
+    pub fn rotate_hue(&self, degrees: f64) -> Color {
+        let (h,_,_) = self.to_hsl();
+        self.with_hue(h + degrees)
+    }
+    /// Invert each RGB channel, 1.0 - value
+    ///
+    ///   Alpha is unchanged
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let c = Color::from_rgb255(255, 128, 0);
+    /// assert_eq!(c.invert().to_hex(), "#007fff");
+    /// ```
+    pub fn invert(&self) -> Color {
+        Color::new(1.0 - self.red, 1.0 - self.green, 1.0 - self.blue, self.alpha)
+    }
+    /// Invert CIE Lab lightness, L becomes 100 - L, keeping a and b
+    ///
+    ///   Unlike invert() the hue is preserved, so light colors become
+    ///   dark colors of the same hue.  Alpha is unchanged and the result
+    ///   is clipped to the sRGB gamut
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let white = Color::from_rgb255(255, 255, 255);
+    /// assert!(white.invert_lab().approx_eq(&Color::new(0.0, 0.0, 0.0, 1.0), 1e-6));
+    /// ```
+    pub fn invert_lab(&self) -> Color {
+        let (l,a,b) = self.to_lab();
+        clip(Color::from_lab(100.0 - l, a, b), self.alpha)
+    }
+    /// Convert to gray using the given method
+    ///
+    ///   Luma weights are applied to the gamma encoded components.
+    ///   Alpha is unchanged
+    ///
+    /// ```
+    /// # use tint::{Color, GrayscaleMethod};
+    /// let green = Color::from_rgb255(0, 255, 0);
+    /// assert_eq!(green.to_grayscale(GrayscaleMethod::Rec709).to_rgb255(), (182, 182, 182));
+    /// assert_eq!(green.to_grayscale(GrayscaleMethod::Rec601).to_rgb255(), (149, 149, 149));
+    /// assert_eq!(green.to_grayscale(GrayscaleMethod::Desaturation).to_rgb255(), (127, 127, 127));
+    /// ```
+    pub fn to_grayscale(&self, method: GrayscaleMethod) -> Color {
+        let (r,g,b) = (self.red, self.green, self.blue);
+        let v = match method {
+            GrayscaleMethod::Rec709 => 0.2126 * r + 0.7152 * g + 0.0722 * b,
+            GrayscaleMethod::Rec601 => 0.299 * r + 0.587 * g + 0.114 * b,
+            GrayscaleMethod::Average => (r + g + b) / 3.0,
+            GrayscaleMethod::Lightness => {
+                let (l,_,_) = self.to_lab();
+                Color::from_lab(l, 0.0, 0.0).red
+            },
+            GrayscaleMethod::Desaturation => (r.max(g).max(b) + r.min(g).min(b)) / 2.0,
+        };
+        Color::new(v, v, v, self.alpha)
+    }
+    /// Complementary color, the hue rotated by 180 degrees in HSL
+    ///
+    ///   Alpha is unchanged
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let red = Color::from_rgb255(255, 0, 0);
+    /// let cyan = Color::from_rgb255(0, 255, 255);
+    /// assert!(red.complement().approx_eq(&cyan, 1e-9));
+    /// ```
+    pub fn complement(&self) -> Color {
+        self.rotate_hue(180.0)
+    }
+    /// Complementary color, the hue rotated by 180 degrees in Oklch
+    ///
+    ///   Lightness and chroma are kept, so the pair is perceptually
+    ///   balanced.  Alpha is unchanged and the result is clipped to the
+    ///   sRGB gamut
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let c = Color::from_rgb255(70, 100, 150);
+    /// let (l0, c0, h0) = c.to_oklch();
+    /// let (l1, c1, h1) = c.complement_oklch().to_oklch();
+    /// assert!((l1 - l0).abs() < 1e-6 && (c1 - c0).abs() < 1e-6);
+    /// assert!(((h1 - h0).rem_euclid(360.0) - 180.0).abs() < 1e-4);
+    /// ```
+    pub fn complement_oklch(&self) -> Color {
+        let (l,c,h) = self.to_oklch();
+        clip(Color::from_oklch(l, c, (h + 180.0).rem_euclid(360.0)), self.alpha)
+    }
+}
+
+/// Clip components to [0,1] and set alpha
+pub(crate) fn clip(c: Color, alpha: f64) -> Color {
+    Color::new(c.red.clamp(0.0, 1.0), c.green.clamp(0.0, 1.0),
+               c.blue.clamp(0.0, 1.0), alpha)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn lightness() {
+        let c = Color::new(0.2, 0.4, 0.6, 0.5);
+        assert_eq!(c.lighten(1.0).to_hex(), "#ffffff");
+        assert_eq!(c.darken(1.0).to_hex(), "#000000");
+        assert_eq!(c.lighten(0.1).alpha, 0.5);
+        assert!(c.lighten(0.1).darken(0.1).approx_eq(&c, 1e-12));
+        assert!(c.lighten_lab(0.1).darken_lab(0.1).approx_eq(&c, 1e-9));
+        assert!(c.lighten_lab(1.0).to_lab().0 > 90.0);
+    }
+    #[test]
+    fn saturation() {
+        let c = Color::new(0.2, 0.4, 0.6, 0.5);
+        let g = c.grayscale();
+        assert_eq!(g.red, g.green);
+        assert_eq!(g.green, g.blue);
+        assert_eq!(g.alpha, 0.5);
+        assert!(c.desaturate(1.0).approx_eq(&g, 1e-12));
+        assert_eq!(c.saturate(1.0).to_hsl().1, 1.0);
+    }
+    #[test]
+    fn hue() {
+        let c = Color::new(0.2, 0.4, 0.6, 0.5);
+        assert!(c.rotate_hue(360.0).approx_eq(&c, 1e-12));
+        assert!(c.rotate_hue(-720.0).approx_eq(&c, 1e-12));
+        assert!(c.rotate_hue(90.0).rotate_hue(-90.0).approx_eq(&c, 1e-12));
+        assert_eq!(c.rotate_hue(45.0).alpha, 0.5);
+        assert!(c.complement().complement().approx_eq(&c, 1e-12));
+        let s = Color::new(0.45, 0.5, 0.55, 0.5);
+        assert!(s.complement_oklch().complement_oklch().approx_eq(&s, 1e-6));
+        assert_eq!(c.complement_oklch().alpha, 0.5);
+    }
+    #[test]
+    fn inversion() {
+        let c = Color::new(0.2, 0.4, 0.6, 0.5);
+        assert!(c.invert().approx_eq(&Color::new(0.8, 0.6, 0.4, 0.5), 1e-12));
+        assert!(c.invert().invert().approx_eq(&c, 1e-12));
+        assert!(c.invert_lab().invert_lab().approx_eq(&c, 1e-9));
+        assert_eq!(c.invert_lab().alpha, 0.5);
+    }
+    #[test]
+    fn grayscale_methods() {
+        let c = Color::new(0.2, 0.4, 0.6, 0.5);
+        assert!(c.to_grayscale(GrayscaleMethod::Desaturation).approx_eq(&c.grayscale(), 1e-12));
+        let g = c.to_grayscale(GrayscaleMethod::Lightness);
+        assert!((g.to_lab().0 - c.to_lab().0).abs() < 1e-6);
+        assert_eq!(g.alpha, 0.5);
+        let w = Color::new(1.0, 1.0, 1.0, 1.0);
+        assert!(w.to_grayscale(GrayscaleMethod::default()).approx_eq(&w, 1e-12));
+    }
+}
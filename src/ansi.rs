@@ -0,0 +1,177 @@
+//! Terminal colors
+
+use Color;
+
+/// xterm default colors for the 16 standard ANSI colors
+static ANSI16: [(u8, u8, u8); 16] = [
+    (0x00, 0x00, 0x00), (0xcd, 0x00, 0x00), (0x00, 0xcd, 0x00), (0xcd, 0xcd, 0x00),
+    (0x00, 0x00, 0xee), (0xcd, 0x00, 0xcd), (0x00, 0xcd, 0xcd), (0xe5, 0xe5, 0xe5),
+    (0x7f, 0x7f, 0x7f), (0xff, 0x00, 0x00), (0x00, 0xff, 0x00), (0xff, 0xff, 0x00),
+    (0x5c, 0x5c, 0xff), (0xff, 0x00, 0xff), (0x00, 0xff, 0xff), (0xff, 0xff, 0xff),
+];
+
+/// Component levels of the xterm 6x6x6 color cube, indices 16 - 231
+static CUBE: [u8; 6] = [0x00, 0x5f, 0x87, 0xaf, 0xd7, 0xff];
+
+/// Index of the closest cube level to an 8 bit component
+fn cube_level(v: f64) -> usize {
+    let v = v.clamp(0.0, 1.0) * 255.0;
+    CUBE.iter().enumerate()
+        .fold((0, f64::INFINITY), |best, (i, &c)| {
+            let d = (c as f64 - v).abs();
+            if d < best.1 { (i, d) } else { best }
+        }).0
+}
+
+/// Index of the closest color by CIEDE2000, the first on ties
+fn nearest<I>(color: &Color, candidates: I) -> usize
+    where I: Iterator<Item = Color>
+{
+    candidates.enumerate()
+        .map(|(i, c)| (i, color.delta_e2000(&c)))
+        .fold((0, f64::INFINITY), |best, x| if x.1 < best.1 { x } else { best })
+        .0
+}
+
+/// Render colors as a row of blocks for a truecolor terminal
+///
+///   Each color is drawn as two spaces on its background color,
+///   followed by a reset.  An empty slice gives an empty string
+///
+/// ```
+/// # use tint::{swatch, Color};
+/// let palette = vec![Color::from("red"), Color::from("blue")];
+/// println!("{}", swatch(&palette));
+/// assert_eq!(swatch(&[Color::from("red")]), "\x1b[48;2;255;0;0m  \x1b[0m");
+/// ```
+pub fn swatch(colors: &[Color]) -> String {
+    if colors.is_empty() {
+        return String::new();
+    }
+    let mut s: String = colors.iter().map(|c| format!("{}  ", c.to_ansi_bg())).collect();
+    s.push_str("\x1b[0m");
+    s
+}
+
+impl Color {
+    /// Closest of the 16 standard ANSI terminal colors, [0,15]
+    ///
+    ///   0 - 7 are the normal colors black, red, green, yellow, blue,
+    ///   magenta, cyan and white, 8 - 15 are their bright versions.
+    ///   Colors are matched against the xterm defaults with CIEDE2000;
+    ///   alpha is ignored
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// assert_eq!(Color::from("black").to_ansi16(), 0);
+    /// assert_eq!(Color::from("darkred").to_ansi16(), 1);
+    /// assert_eq!(Color::from("white").to_ansi16(), 15);
+    /// ```
+    pub fn to_ansi16(&self) -> u8 {
+        nearest(self, ANSI16.iter().map(|&(r,g,b)| Color::from_rgb255(r, g, b))) as u8
+    }
+    /// Closest xterm 256 color, from the color cube or gray ramp, [16,255]
+    ///
+    ///   The 16 standard colors, 0 - 15, are not used as their values
+    ///   vary between terminals.  The closest cube color and the closest
+    ///   gray are compared with CIEDE2000; alpha is ignored
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// assert_eq!(Color::from("red").to_ansi256(), 196);
+    /// assert_eq!(Color::from_rgb255(128, 128, 128).to_ansi256(), 244);
+    /// ```
+    pub fn to_ansi256(&self) -> u8 {
+        let (r, g, b) = (cube_level(self.red), cube_level(self.green), cube_level(self.blue));
+        let cube = 16 + 36 * r + 6 * g + b;
+        let mean = (self.red + self.green + self.blue) / 3.0 * 255.0;
+        let gray = 232 + ((mean - 8.0) / 10.0).round().clamp(0.0, 23.0) as usize;
+        let candidates = [cube, gray];
+        candidates[nearest(self, candidates.iter().map(|&i| Color::from_ansi256(i as u8)))] as u8
+    }
+    /// Create new Color from an xterm 256 color index
+    ///   alpha value set to 1.0
+    ///
+    ///   0 - 15 use the xterm default colors, 16 - 231 are the 6x6x6
+    ///   color cube and 232 - 255 the gray ramp
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// assert_eq!(Color::from_ansi256(196).to_hex(), "#ff0000");
+    /// assert_eq!(Color::from_ansi256(67).to_hex(), "#5f87af");
+    /// assert_eq!(Color::from_ansi256(232).to_hex(), "#080808");
+    /// ```
+    pub fn from_ansi256(index: u8) -> Color {
+        let i = index as usize;
+        match i {
+            0..=15 => {
+                let (r, g, b) = ANSI16[i];
+                Color::from_rgb255(r, g, b)
+            },
+            16..=231 => {
+                let i = i - 16;
+                Color::from_rgb255(CUBE[i / 36], CUBE[(i / 6) % 6], CUBE[i % 6])
+            },
+            _ => {
+                let v = (8 + 10 * (i - 232)) as u8;
+                Color::from_rgb255(v, v, v)
+            },
+        }
+    }
+    /// 24 bit SGR escape sequence setting the terminal foreground color
+    ///
+    ///   Reset with "\x1b[0m".  Alpha is ignored
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let red = Color::from("red");
+    /// assert_eq!(red.to_ansi_fg(), "\x1b[38;2;255;0;0m");
+    /// println!("{}error\x1b[0m", red.to_ansi_fg());
+    /// ```
+    pub fn to_ansi_fg(&self) -> String {
+        let (r, g, b) = self.to_rgb255();
+        format!("\x1b[38;2;{};{};{}m", r, g, b)
+    }
+    /// 24 bit SGR escape sequence setting the terminal background color
+    ///
+    ///   Reset with "\x1b[0m".  Alpha is ignored
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// assert_eq!(Color::from("navy").to_ansi_bg(), "\x1b[48;2;0;0;128m");
+    /// ```
+    pub fn to_ansi_bg(&self) -> String {
+        let (r, g, b) = self.to_rgb255();
+        format!("\x1b[48;2;{};{};{}m", r, g, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn ansi16() {
+        for (i, &(r,g,b)) in ANSI16.iter().enumerate() {
+            assert_eq!(Color::from_rgb255(r, g, b).to_ansi16() as usize, i);
+        }
+        assert_eq!(Color::from_rgb255(250, 250, 250).to_ansi16(), 15);
+        assert_eq!(Color::from("dimgray").to_ansi16(), 8);
+    }
+    #[test]
+    fn ansi256() {
+        for i in 16..=255 {
+            assert_eq!(Color::from_ansi256(i).to_ansi256(), i, "{}", i);
+        }
+        for i in 0..16 {
+            assert!(Color::from_ansi256(i).to_ansi256() >= 16);
+        }
+        assert_eq!(Color::from("black").to_ansi256(), 16);
+        assert_eq!(Color::from("white").to_ansi256(), 231);
+    }
+    #[test]
+    fn swatches() {
+        assert_eq!(swatch(&[]), "");
+        let s = swatch(&[Color::from("red"), Color::from("lime")]);
+        assert_eq!(s, "\x1b[48;2;255;0;0m  \x1b[48;2;0;255;0m  \x1b[0m");
+    }
+}
@@ -0,0 +1,494 @@
+//! Whole buffer conversions between packed pixels and Colors
+//!
+//! Pixels are packed 8-bit components, red, green, blue and optionally
+//! alpha, as used by image crates and frame buffers.  Incomplete
+//! trailing pixels are ignored.
+//!
+//! ```
+//! let pixels = [255, 0, 0, 0, 0, 255];
+//! let colors = tint::batch::rgb8_to_colors(&pixels);
+//! assert_eq!(colors[1].to_hex(), "#0000ff");
+//!
+//! let mut out = [0u8; 8];
+//! tint::batch::colors_to_rgba8(&colors, &mut out);
+//! assert_eq!(out, [255, 0, 0, 255, 0, 0, 255, 255]);
+//! ```
+//!
+//! The transfer function, HSV and hex conversions work on packed
+//! buffers directly, without converting through Color.  They use
+//! lookup tables and single precision arithmetic rather than the f64
+//! of Color, and SIMD instructions where the CPU has them: AVX2 and
+//! SSSE3 on x86_64, detected at runtime, and NEON on aarch64 for the
+//! HSV and hex conversions.  Results are the same either way.
+//!
+//! With the "rayon" feature, buffers are split into blocks that are
+//! converted in parallel.
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use name_index;
+use simd;
+use Color;
+use space::{linear_to_srgb, srgb_to_linear};
+
+/// Pixels per block, the unit of parallel work
+const BLOCK: usize = 16384;
+/// Buckets in the linear to sRGB table
+const BUCKETS: usize = 4096;
+
+lazy_static! {
+    static ref TO_LINEAR: [f32;256] = {
+        let mut t = [0.0; 256];
+        for (i, v) in t.iter_mut().enumerate() {
+            *v = srgb_to_linear(i as f64 / 255.0) as f32;
+        }
+        t
+    };
+    // Smallest linear value truncating to each sRGB level
+    static ref THRESHOLD: [f32;257] = {
+        let mut t = [0.0; 257];
+        for (k, v) in t.iter_mut().enumerate().skip(1).take(255) {
+            *v = srgb_to_linear(k as f64 / 255.0) as f32;
+        }
+        t[256] = f32::INFINITY;
+        t
+    };
+    // sRGB level at the start of each bucket; the steepest slope of the
+    // transfer function spans less than one level per bucket
+    static ref TO_SRGB: [i32;BUCKETS] = {
+        let mut t = [0; BUCKETS];
+        for (i, v) in t.iter_mut().enumerate() {
+            *v = (linear_to_srgb(i as f64 / BUCKETS as f64) * 255.0) as u8 as i32;
+        }
+        t
+    };
+}
+
+static HEX_DIGITS: &[u8;16] = b"0123456789abcdef";
+
+/// Component values [0,1] for each u8
+fn unit_table() -> [f64;256] {
+    let mut t = [0.0; 256];
+    for (i, v) in t.iter_mut().enumerate() {
+        *v = i as f64 / 255.0;
+    }
+    t
+}
+
+/// Convert packed RGB pixels into Colors, alpha set to 1.0
+pub fn rgb8_to_colors(pixels: &[u8]) -> Vec<Color> {
+    let t = unit_table();
+    let mut colors = vec![Color::default(); pixels.len() / 3];
+    blocks(pixels, 3, &mut colors, 1, |s, o| {
+        for (p, c) in s.chunks_exact(3).zip(o.iter_mut()) {
+            *c = Color::new(t[p[0] as usize], t[p[1] as usize], t[p[2] as usize], 1.0);
+        }
+    });
+    colors
+}
+
+/// Convert packed RGBA pixels into Colors
+pub fn rgba8_to_colors(pixels: &[u8]) -> Vec<Color> {
+    let t = unit_table();
+    let mut colors = vec![Color::default(); pixels.len() / 4];
+    blocks(pixels, 4, &mut colors, 1, |s, o| {
+        for (p, c) in s.chunks_exact(4).zip(o.iter_mut()) {
+            *c = Color::new(t[p[0] as usize], t[p[1] as usize], t[p[2] as usize], t[p[3] as usize]);
+        }
+    });
+    colors
+}
+
+/// Write Colors as packed RGB pixels, alpha is ignored
+///
+///   Components are converted as in Color::to_rgb255
+///
+/// # Panics
+///   If out is shorter than 3 * colors.len()
+pub fn colors_to_rgb8(colors: &[Color], out: &mut [u8]) {
+    assert!(out.len() >= colors.len() * 3, "output buffer too small");
+    blocks(colors, 1, out, 3, |s, o| {
+        for (c, p) in s.iter().zip(o.chunks_exact_mut(3)) {
+            p[0] = (c.red   * 255.0) as u8;
+            p[1] = (c.green * 255.0) as u8;
+            p[2] = (c.blue  * 255.0) as u8;
+        }
+    });
+}
+
+/// Write Colors as packed RGBA pixels
+///
+///   Components are converted as in Color::to_rgba8
+///
+/// # Panics
+///   If out is shorter than 4 * colors.len()
+pub fn colors_to_rgba8(colors: &[Color], out: &mut [u8]) {
+    assert!(out.len() >= colors.len() * 4, "output buffer too small");
+    blocks(colors, 1, out, 4, |s, o| {
+        for (c, p) in s.iter().zip(o.chunks_exact_mut(4)) {
+            p[0] = (c.red   * 255.0) as u8;
+            p[1] = (c.green * 255.0) as u8;
+            p[2] = (c.blue  * 255.0) as u8;
+            p[3] = (c.alpha * 255.0) as u8;
+        }
+    });
+}
+
+/// Convert packed 8-bit sRGB components to linear light
+///
+///   Every byte is converted, so alpha in RGBA pixels should be
+///   restored by the caller
+///
+/// # Panics
+///   If out is shorter than pixels
+pub fn srgb8_to_linear(pixels: &[u8], out: &mut [f32]) {
+    assert!(out.len() >= pixels.len(), "output buffer too small");
+    let t = &*TO_LINEAR;
+    blocks(pixels, 1, out, 1, |s, o| {
+        let n = simd::srgb8_to_linear(s, o, t);
+        for (p, v) in s[n..].iter().zip(o[n..].iter_mut()) {
+            *v = t[*p as usize];
+        }
+    });
+}
+
+/// Convert linear light components to packed 8-bit sRGB
+///
+///   Values are clamped to [0,1] and truncated, as in Color::to_rgb255()
+///
+/// ```
+/// let mut out = [0u8; 3];
+/// tint::batch::linear_to_srgb8(&[0.0, 0.216, 1.0], &mut out);
+/// assert_eq!(out, [0, 128, 255]);
+/// ```
+///
+/// # Panics
+///   If out is shorter than values
+pub fn linear_to_srgb8(values: &[f32], out: &mut [u8]) {
+    assert!(out.len() >= values.len(), "output buffer too small");
+    let lut = &*TO_SRGB;
+    let th = &*THRESHOLD;
+    blocks(values, 1, out, 1, |s, o| {
+        let n = simd::linear_to_srgb8(s, o, lut, th);
+        for (x, p) in s[n..].iter().zip(o[n..].iter_mut()) {
+            *p = to_srgb8(*x, lut, th);
+        }
+    });
+}
+
+fn to_srgb8(x: f32, lut: &[i32;BUCKETS], th: &[f32;257]) -> u8 {
+    let x = x.clamp(0.0, 1.0);
+    let i = ((x * BUCKETS as f32) as usize).min(BUCKETS - 1);
+    let k = lut[i] as usize;
+    (k + (x >= th[k + 1]) as usize) as u8
+}
+
+/// Convert packed RGB [0,1] to HSV, h : [0, 360],  s : [0, 1],  v : [0, 1]
+///
+///   Values match Color::to_hsv to single precision
+///
+/// # Panics
+///   If out is shorter than rgb
+pub fn rgb_to_hsv(rgb: &[f32], out: &mut [f32]) {
+    triples(rgb, out, simd::rgb_to_hsv, rgb_to_hsv1);
+}
+
+fn rgb_to_hsv1(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+    let max = r.max(g).max(b);
+    let d = max - r.min(g).min(b);
+    let hr = (g - b) / d;
+    let hg = 2.0 + (b - r) / d;
+    let hb = 4.0 + (r - g) / d;
+    let h = 60.0 * if r >= max { hr } else if g >= max { hg } else { hb };
+    let h = if h < 0.0 { h + 360.0 } else { h };
+    let gray = d < 1e-5;
+    (if gray { 0.0 } else { h }, if gray { 0.0 } else { d / max }, max)
+}
+
+/// Convert packed HSV, h : [0, 360],  s : [0, 1],  v : [0, 1], to RGB
+///
+/// ```
+/// let mut rgb = [0.0; 3];
+/// tint::batch::hsv_to_rgb(&[300.0, 1.0, 1.0], &mut rgb);
+/// assert_eq!(rgb, [1.0, 0.0, 1.0]);
+/// ```
+///
+/// # Panics
+///   If out is shorter than hsv
+pub fn hsv_to_rgb(hsv: &[f32], out: &mut [f32]) {
+    triples(hsv, out, simd::hsv_to_rgb, hsv_to_rgb1);
+}
+
+fn hsv_to_rgb1(h: f32, s: f32, v: f32) -> (f32, f32, f32) {
+    let f = |n: f32| {
+        let k = n + h / 60.0;
+        let k = if k >= 6.0 { k - 6.0 } else { k };
+        v - v * s * k.min(4.0 - k).clamp(0.0, 1.0)
+    };
+    (f(5.0), f(3.0), f(1.0))
+}
+
+/// Write packed RGB pixels as ASCII hex codes, "#rrggbb", 7 bytes per pixel
+///
+/// ```
+/// let mut out = [0u8; 14];
+/// tint::batch::rgb8_to_hex(&[255, 0, 255, 0, 128, 0], &mut out);
+/// assert_eq!(std::str::from_utf8(&out), Ok("#ff00ff#008000"));
+/// ```
+///
+/// # Panics
+///   If out is shorter than 7 bytes per pixel
+pub fn rgb8_to_hex(pixels: &[u8], out: &mut [u8]) {
+    assert!(out.len() >= pixels.len() / 3 * 7, "output buffer too small");
+    blocks(pixels, 3, out, 7, |s, o| {
+        let n = simd::rgb8_to_hex(s, o);
+        for (p, h) in s[3 * n..].chunks_exact(3).zip(o[7 * n..].chunks_exact_mut(7)) {
+            hex7(p, h);
+        }
+    });
+}
+
+fn hex7(p: &[u8], h: &mut [u8]) {
+    h[0] = b'#';
+    for (i, v) in p.iter().enumerate() {
+        h[1 + 2 * i] = HEX_DIGITS[(v >> 4) as usize];
+        h[2 + 2 * i] = HEX_DIGITS[(v & 0xf) as usize];
+    }
+}
+
+/// Closest named color and CIE76 distance for each Color, as in
+/// Color::nearest_name_cie76()
+///
+/// ```
+/// let colors = [tint::Color::from_rgb255(250, 10, 5), tint::Color::from("navy")];
+/// let names: Vec<_> = tint::batch::nearest_names(&colors).into_iter().map(|n| n.0).collect();
+/// assert_eq!(names, vec!["red", "navy"]);
+/// ```
+///
+/// # Panics
+///   If the named color database is empty
+pub fn nearest_names(colors: &[Color]) -> Vec<(String, f64)> {
+    let index = name_index();
+    let mut names = vec![(String::new(), 0.0); colors.len()];
+    blocks(colors, 1, &mut names, 1, |s, o| {
+        for (c, n) in s.iter().zip(o.iter_mut()) {
+            *n = index.nearest(c).expect("named color database is empty");
+        }
+    });
+    names
+}
+
+/// Convert packed triples with a SIMD kernel, then apply f to those left
+fn triples(src: &[f32], out: &mut [f32], kernel: fn(&[f32], &mut [f32]) -> usize,
+           f: fn(f32, f32, f32) -> (f32, f32, f32)) {
+    assert!(out.len() >= src.len(), "output buffer too small");
+    blocks(src, 3, out, 3, |src, out| {
+        let n = kernel(src, out);
+        for (s, o) in src[n..].chunks_exact(3).zip(out[n..].chunks_exact_mut(3)) {
+            let (x, y, z) = f(s[0], s[1], s[2]);
+            o[0] = x;
+            o[1] = y;
+            o[2] = z;
+        }
+    });
+}
+
+/// Apply f to matching blocks of BLOCK pixels from src and out, with n
+///   and m elements per pixel.  Blocks run in parallel with the "rayon"
+///   feature
+pub(crate) fn blocks<T, U, F>(src: &[T], n: usize, out: &mut [U], m: usize, f: F)
+    where T: Sync, U: Send, F: Fn(&[T], &mut [U]) + Sync
+{
+    #[cfg(feature = "rayon")]
+    src.par_chunks(BLOCK * n).zip(out.par_chunks_mut(BLOCK * m)).for_each(|(s, o)| f(s, o));
+    #[cfg(not(feature = "rayon"))]
+    for (s, o) in src.chunks(BLOCK * n).zip(out.chunks_mut(BLOCK * m)) {
+        f(s, o);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn matches_scalar() {
+        let pixels: Vec<u8> = (0..=255).chain(0..=255).collect();
+        let colors = rgba8_to_colors(&pixels);
+        assert_eq!(colors.len(), 128);
+        for (c, p) in colors.iter().zip(pixels.chunks_exact(4)) {
+            assert_eq!(*c, Color::from([p[0], p[1], p[2], p[3]]));
+        }
+        let mut out = vec![0; pixels.len()];
+        colors_to_rgba8(&colors, &mut out);
+        assert_eq!(out, pixels);
+
+        let colors = rgb8_to_colors(&pixels[..301]);
+        assert_eq!(colors.len(), 100);
+        let mut out = vec![0; 300];
+        colors_to_rgb8(&colors, &mut out);
+        assert_eq!(out, &pixels[..300]);
+    }
+    #[test]
+    #[should_panic]
+    fn short_output() {
+        colors_to_rgba8(&[Color::default()], &mut [0; 3]);
+    }
+    #[test]
+    fn transfer() {
+        let pixels: Vec<u8> = (0..=255).collect();
+        let mut lin = vec![0.0; 256];
+        srgb8_to_linear(&pixels, &mut lin);
+        let mut back = vec![0; 256];
+        linear_to_srgb8(&lin, &mut back);
+        assert_eq!(back, pixels);
+        let values: Vec<f32> = (0..=100_000).map(|i| i as f32 / 100_000.0).collect();
+        let mut out = vec![0; values.len()];
+        linear_to_srgb8(&values, &mut out);
+        for (x, p) in values.iter().zip(out.iter()) {
+            // Allow for rounding error at the level boundaries
+            let v = linear_to_srgb(*x as f64) * 255.0;
+            assert!((v - 1e-9) as u8 <= *p && *p <= (v + 1e-9) as u8, "{} {}", x, p);
+        }
+    }
+    #[test]
+    fn hsv() {
+        let rgb: Vec<f32> = (0..999).map(|i| (i * 37 % 101) as f32 / 100.0).collect();
+        let mut hsv = vec![0.0; rgb.len()];
+        rgb_to_hsv(&rgb, &mut hsv);
+        let mut back = vec![0.0; rgb.len()];
+        hsv_to_rgb(&hsv, &mut back);
+        for ((p, q), b) in rgb.chunks(3).zip(hsv.chunks(3)).zip(back.chunks(3)) {
+            let (h, s, v) = Color::from_rgb1(p[0] as f64, p[1] as f64, p[2] as f64).to_hsv();
+            assert!((h - q[0] as f64).abs() < 1e-3 && (s - q[1] as f64).abs() < 1e-5);
+            assert_eq!(v, q[2] as f64);
+            for i in 0..3 {
+                assert!((p[i] - b[i]).abs() < 1e-5);
+            }
+        }
+    }
+    #[test]
+    fn hex() {
+        let pixels: Vec<u8> = (0..=255).cycle().take(300).collect();
+        let mut out = vec![0; 700];
+        rgb8_to_hex(&pixels, &mut out);
+        for (h, p) in out.chunks(7).zip(pixels.chunks(3)) {
+            assert_eq!(h, Color::from_rgb255(p[0], p[1], p[2]).to_hex().as_bytes());
+        }
+    }
+    #[test]
+    fn blocks() {
+        let pixels: Vec<u8> = (0..3 * (2 * BLOCK + 5)).map(|i| (i * 7 % 256) as u8).collect();
+        let colors = rgb8_to_colors(&pixels);
+        assert_eq!(colors[BLOCK + 1], Color::from_rgb255v(&pixels[3 * BLOCK + 3..]));
+        let mut out = vec![0; pixels.len()];
+        colors_to_rgb8(&colors, &mut out);
+        assert_eq!(out, pixels);
+        let names = nearest_names(&colors[..100]);
+        for (c, n) in colors.iter().zip(names.iter()) {
+            assert_eq!(c.nearest_name_cie76(), *n);
+        }
+    }
+    #[test]
+    fn simd_matches_scalar() {
+        // Lengths leave tails for the scalar loops
+        let bytes: Vec<u8> = (0..1001).map(|i| (i * 37 % 256) as u8).collect();
+        let mut lin = vec![0.0; bytes.len()];
+        srgb8_to_linear(&bytes, &mut lin);
+        for (p, v) in bytes.iter().zip(lin.iter()) {
+            assert_eq!(*v, TO_LINEAR[*p as usize]);
+        }
+
+        let mut values: Vec<f32> = (0..1003).map(|i| i as f32 / 1000.0 - 0.001).collect();
+        values.extend(THRESHOLD[1..256].iter());
+        values.extend([f32::NAN, -1.0, 2.0, f32::INFINITY, f32::NEG_INFINITY].iter());
+        let mut out = vec![0; values.len()];
+        linear_to_srgb8(&values, &mut out);
+        for (x, p) in values.iter().zip(out.iter()) {
+            assert_eq!(*p, to_srgb8(*x, &TO_SRGB, &THRESHOLD), "{}", x);
+        }
+
+        // Grays, ties between maxima and every sextant of hue
+        let rgb: Vec<f32> = (0..1331).flat_map(|i| vec![i / 121, i / 11 % 11, i % 11])
+            .map(|v| v as f32 / 10.0).chain(vec![0.5, 0.25]).collect();
+        let mut hsv = vec![0.0; rgb.len()];
+        rgb_to_hsv(&rgb, &mut hsv);
+        let mut back = vec![0.0; rgb.len()];
+        hsv_to_rgb(&hsv, &mut back);
+        for ((p, q), b) in rgb.chunks_exact(3).zip(hsv.chunks_exact(3)).zip(back.chunks_exact(3)) {
+            assert_eq!((q[0], q[1], q[2]), rgb_to_hsv1(p[0], p[1], p[2]), "{:?}", p);
+            assert_eq!((b[0], b[1], b[2]), hsv_to_rgb1(q[0], q[1], q[2]), "{:?}", q);
+        }
+
+        let mut hex = vec![0; bytes.len() / 3 * 7];
+        rgb8_to_hex(&bytes, &mut hex);
+        let mut h = [0; 7];
+        for (p, x) in bytes.chunks_exact(3).zip(hex.chunks_exact(7)) {
+            hex7(p, &mut h);
+            assert_eq!(x, h);
+        }
+    }
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn simd_kernels_run() {
+        if is_x86_feature_detected!("avx2") {
+            assert_eq!(simd::srgb8_to_linear(&[0; 9], &mut [0.0; 9], &TO_LINEAR), 8);
+            assert_eq!(simd::linear_to_srgb8(&[0.0; 9], &mut [0; 9], &*TO_SRGB, &THRESHOLD), 8);
+            assert_eq!(simd::rgb_to_hsv(&[0.0; 27], &mut [0.0; 27]), 24);
+            assert_eq!(simd::hsv_to_rgb(&[0.0; 27], &mut [0.0; 27]), 24);
+        }
+        if is_x86_feature_detected!("ssse3") {
+            assert_eq!(simd::rgb8_to_hex(&[0; 27], &mut [0; 63]), 4);
+        }
+    }
+    /// Scalar loops against the SIMD kernels on a 3840x2160 frame, run with
+    ///   cargo test --release --lib bench_4k -- --ignored --nocapture
+    #[test]
+    #[ignore]
+    fn bench_4k() {
+        use std::time::Instant;
+        fn best<F: FnMut()>(mut f: F) -> f64 {
+            (0..5).map(|_| {
+                let t = Instant::now();
+                f();
+                t.elapsed().as_secs_f64() * 1e3
+            }).fold(f64::INFINITY, f64::min)
+        }
+        fn report(name: &str, scalar: f64, simd: f64) {
+            println!("{:16} scalar {:7.2} ms  simd {:7.2} ms  {:5.2}x", name, scalar, simd, scalar / simd);
+        }
+        let n = 3840 * 2160;
+        let bytes: Vec<u8> = (0..3 * n).map(|i| (i * 7919 % 251) as u8).collect();
+        let values: Vec<f32> = bytes.iter().map(|&v| v as f32 / 255.0).collect();
+        let (t, lut, th) = (&*TO_LINEAR, &*TO_SRGB, &*THRESHOLD);
+
+        let mut lin = vec![0.0; 3 * n];
+        report("srgb8_to_linear",
+               best(|| for (p, v) in bytes.iter().zip(lin.iter_mut()) { *v = t[*p as usize] }),
+               best(|| { let k = simd::srgb8_to_linear(&bytes, &mut lin, t); assert_eq!(k, 3 * n) }));
+        let mut out = vec![0; 3 * n];
+        report("linear_to_srgb8",
+               best(|| for (x, p) in values.iter().zip(out.iter_mut()) { *p = to_srgb8(*x, lut, th) }),
+               best(|| { let k = simd::linear_to_srgb8(&values, &mut out, lut, th); assert_eq!(k, 3 * n) }));
+        let apply = |f: fn(f32, f32, f32) -> (f32, f32, f32), src: &[f32], out: &mut [f32]| {
+            for (s, o) in src.chunks_exact(3).zip(out.chunks_exact_mut(3)) {
+                let (x, y, z) = f(s[0], s[1], s[2]);
+                o[0] = x;
+                o[1] = y;
+                o[2] = z;
+            }
+        };
+        let mut hsv = vec![0.0; 3 * n];
+        report("rgb_to_hsv",
+               best(|| apply(rgb_to_hsv1, &values, &mut hsv)),
+               best(|| { let k = simd::rgb_to_hsv(&values, &mut hsv); assert_eq!(k, 3 * n) }));
+        let mut rgb = vec![0.0; 3 * n];
+        report("hsv_to_rgb",
+               best(|| apply(hsv_to_rgb1, &hsv, &mut rgb)),
+               best(|| { let k = simd::hsv_to_rgb(&hsv, &mut rgb); assert_eq!(k, 3 * n) }));
+        let mut hex = vec![0; 7 * n];
+        report("rgb8_to_hex",
+               best(|| for (p, h) in bytes.chunks_exact(3).zip(hex.chunks_exact_mut(7)) { hex7(p, h) }),
+               best(|| { let k = simd::rgb8_to_hex(&bytes, &mut hex); assert_eq!(k, n - 4) }));
+    }
+}
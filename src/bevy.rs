@@ -0,0 +1,39 @@
+//! Conversions to and from bevy colors, with the "bevy" feature
+
+use bevy_color::{Color as BevyColor, LinearRgba, Srgba};
+use Color;
+
+impl From<Color> for BevyColor {
+    fn from(c: Color) -> BevyColor {
+        BevyColor::srgba(c.red as f32, c.green as f32, c.blue as f32, c.alpha as f32)
+    }
+}
+
+impl From<Color> for LinearRgba {
+    fn from(c: Color) -> LinearRgba {
+        let [r, g, b, a] = c.to_linear_f32_array();
+        LinearRgba::new(r, g, b, a)
+    }
+}
+
+impl From<BevyColor> for Color {
+    fn from(c: BevyColor) -> Color {
+        let Srgba { red, green, blue, alpha } = c.to_srgba();
+        Color::new(red as f64, green as f64, blue as f64, alpha as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn bevy() {
+        let c = Color::new(0.25, 0.5, 0.75, 0.5);
+        let back = Color::from(BevyColor::from(c));
+        assert_eq!(back.to_hex_rgba(), c.to_hex_rgba());
+        let lin = LinearRgba::from(c);
+        let v = BevyColor::from(c).to_linear();
+        assert!((lin.green - v.green).abs() < 1e-6);
+        assert_eq!(lin.alpha, 0.5);
+    }
+}
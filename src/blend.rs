@@ -0,0 +1,191 @@
+//! Separable blend modes from W3C Compositing and Blending Level 1
+//!
+//! https://www.w3.org/TR/compositing-1/#blending
+
+use AlphaMode;
+use Color;
+
+/// Separable blend mode used by Color::blend()
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Hash,Default)]
+pub enum BlendMode {
+    /// Source color, the default
+    #[default]
+    Normal,
+    /// Product of source and backdrop, always darker
+    Multiply,
+    /// Inverse product of inverses, always lighter
+    Screen,
+    /// Multiply or screen depending on the backdrop
+    Overlay,
+    /// Smaller of source and backdrop
+    Darken,
+    /// Larger of source and backdrop
+    Lighten,
+    /// Brighten the backdrop towards the source
+    ColorDodge,
+    /// Darken the backdrop towards the source
+    ColorBurn,
+    /// Multiply or screen depending on the source
+    HardLight,
+    /// Softer version of hard light
+    SoftLight,
+    /// Absolute difference of source and backdrop
+    Difference,
+    /// Like difference with lower contrast
+    Exclusion,
+}
+
+impl BlendMode {
+    /// All blend modes
+    pub fn all() -> Vec<BlendMode> {
+        use self::BlendMode::*;
+        vec![Normal, Multiply, Screen, Overlay, Darken, Lighten, ColorDodge,
+             ColorBurn, HardLight, SoftLight, Difference, Exclusion]
+    }
+    /// CSS mix-blend-mode name
+    pub fn css_name(&self) -> &'static str {
+        match *self {
+            BlendMode::Normal => "normal",
+            BlendMode::Multiply => "multiply",
+            BlendMode::Screen => "screen",
+            BlendMode::Overlay => "overlay",
+            BlendMode::Darken => "darken",
+            BlendMode::Lighten => "lighten",
+            BlendMode::ColorDodge => "color-dodge",
+            BlendMode::ColorBurn => "color-burn",
+            BlendMode::HardLight => "hard-light",
+            BlendMode::SoftLight => "soft-light",
+            BlendMode::Difference => "difference",
+            BlendMode::Exclusion => "exclusion",
+        }
+    }
+    /// Blend a single backdrop (cb) and source (cs) component
+    fn apply(&self, cb: f64, cs: f64) -> f64 {
+        match *self {
+            BlendMode::Normal => cs,
+            BlendMode::Multiply => cb * cs,
+            BlendMode::Screen => screen(cb, cs),
+            BlendMode::Overlay => hard_light(cs, cb),
+            BlendMode::Darken => cb.min(cs),
+            BlendMode::Lighten => cb.max(cs),
+            BlendMode::ColorDodge => {
+                if cb == 0.0 {
+                    0.0
+                } else if cs >= 1.0 {
+                    1.0
+                } else {
+                    (cb / (1.0 - cs)).min(1.0)
+                }
+            },
+            BlendMode::ColorBurn => {
+                if cb >= 1.0 {
+                    1.0
+                } else if cs <= 0.0 {
+                    0.0
+                } else {
+                    1.0 - ((1.0 - cb) / cs).min(1.0)
+                }
+            },
+            BlendMode::HardLight => hard_light(cb, cs),
+            BlendMode::SoftLight => {
+                if cs <= 0.5 {
+                    cb - (1.0 - 2.0 * cs) * cb * (1.0 - cb)
+                } else {
+                    let d = if cb <= 0.25 {
+                        ((16.0 * cb - 12.0) * cb + 4.0) * cb
+                    } else {
+                        cb.sqrt()
+                    };
+                    cb + (2.0 * cs - 1.0) * (d - cb)
+                }
+            },
+            BlendMode::Difference => (cb - cs).abs(),
+            BlendMode::Exclusion => cb + cs - 2.0 * cb * cs,
+        }
+    }
+}
+
+fn screen(cb: f64, cs: f64) -> f64 {
+    cb + cs - cb * cs
+}
+
+fn hard_light(cb: f64, cs: f64) -> f64 {
+    if cs <= 0.5 {
+        cb * 2.0 * cs
+    } else {
+        screen(cb, 2.0 * cs - 1.0)
+    }
+}
+
+impl Color {
+    /// Blend the Color (source) onto a backdrop with a blend mode
+    ///
+    ///   The blended color is composited over the backdrop, so alpha
+    ///   of both colors is respected as in CSS mix-blend-mode
+    ///
+    /// ```
+    /// # use tint::{BlendMode, Color};
+    /// let src = Color::from_rgb1(1.0, 0.5, 0.0);
+    /// let dst = Color::from_rgb1(0.5, 0.5, 0.5);
+    /// assert_eq!(src.blend(&dst, BlendMode::Multiply), Color::from_rgb1(0.5, 0.25, 0.0));
+    /// assert_eq!(src.blend(&dst, BlendMode::Screen), Color::from_rgb1(1.0, 0.75, 0.5));
+    /// ```
+    pub fn blend(&self, backdrop: &Color, mode: BlendMode) -> Color {
+        let (a_s, a_b) = (self.alpha, backdrop.alpha);
+        let a_o = a_s + a_b * (1.0 - a_s);
+        if a_o <= 0.0 {
+            return Color::new(0.0, 0.0, 0.0, 0.0);
+        }
+        let f = |cb: f64, cs: f64| {
+            let cs = (1.0 - a_b) * cs + a_b * mode.apply(cb, cs);
+            (a_s * cs + a_b * cb * (1.0 - a_s)) / a_o
+        };
+        Color::new(f(backdrop.red, self.red),
+                   f(backdrop.green, self.green),
+                   f(backdrop.blue, self.blue),
+                   a_o)
+    }
+    /// Blend like blend(), with colors in the given alpha representation
+    ///
+    ///   Both inputs and the result use the same representation
+    pub fn blend_as(&self, backdrop: &Color, mode: BlendMode, alpha: AlphaMode) -> Color {
+        let c = alpha.decode(self).blend(&alpha.decode(backdrop), mode);
+        alpha.encode(&c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn modes() {
+        let src = Color::new(0.2, 0.4, 0.6, 1.0);
+        let dst = Color::new(0.7, 0.5, 0.3, 1.0);
+        assert_eq!(src.blend(&dst, BlendMode::Normal), src);
+        assert!(src.blend(&dst, BlendMode::Difference)
+                .approx_eq(&Color::new(0.5, 0.1, 0.3, 1.0), 1e-12));
+        assert!(src.blend(&dst, BlendMode::Overlay)
+                .approx_eq(&dst.blend(&src, BlendMode::HardLight), 1e-12));
+        let white = Color::new(1.0, 1.0, 1.0, 1.0);
+        for mode in BlendMode::all() {
+            let c = src.blend(&dst, mode);
+            assert!(c.channels().all(|v| (0.0..=1.0).contains(&v)), "{:?}", mode);
+            assert!(!mode.css_name().is_empty());
+        }
+        assert_eq!(white.blend(&dst, BlendMode::Multiply), dst);
+    }
+    #[test]
+    fn alpha() {
+        let src = Color::new(1.0, 0.0, 0.0, 0.0);
+        let dst = Color::new(0.0, 0.0, 1.0, 1.0);
+        assert_eq!(src.blend(&dst, BlendMode::Screen), dst);
+        let src = Color::new(1.0, 0.0, 0.0, 1.0);
+        let clear = Color::new(0.0, 0.0, 1.0, 0.0);
+        assert_eq!(src.blend(&clear, BlendMode::Multiply), src);
+        let src = Color::new(0.2, 0.4, 0.6, 0.5);
+        let dst = Color::new(0.7, 0.5, 0.3, 0.8);
+        let p = src.to_premultiplied()
+            .blend_as(&dst.to_premultiplied(), BlendMode::Overlay, AlphaMode::Premultiplied);
+        assert!(p.approx_eq(&src.blend(&dst, BlendMode::Overlay).to_premultiplied(), 1e-12));
+    }
+}
@@ -0,0 +1,115 @@
+//! ColorBrewer palettes
+//!
+//! Sequential, diverging and qualitative palettes by Cynthia Brewer,
+//! http://colorbrewer2.org/
+
+use Color;
+
+static COLORBREWER: &str = include_str!("colorbrewer.txt");
+
+/// Kind of ColorBrewer palette
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Hash)]
+pub enum PaletteType {
+    /// Ordered data progressing from low to high
+    Sequential,
+    /// Data diverging from a critical midpoint
+    Diverging,
+    /// Categories without order
+    Qualitative,
+}
+
+struct Entry {
+    name: String,
+    kind: PaletteType,
+    colors: Vec<Color>,
+}
+
+lazy_static! {
+    static ref PALETTES: Vec<Entry> = read_brewer(COLORBREWER);
+}
+
+/// Get a ColorBrewer palette by name, ignoring case, and number of classes
+///
+///   Returns None if the name is unknown or the palette is not
+///   defined for that number of classes
+///
+/// ```
+/// let blues = tint::brewer("Blues", 3).unwrap();
+/// let hex : Vec<_> = blues.iter().map(|c| c.to_hex()).collect();
+/// assert_eq!(hex, vec!["#deebf7", "#9ecae1", "#3182bd"]);
+/// assert!(tint::brewer("blues", 3).is_some());
+/// assert!(tint::brewer("Blues", 20).is_none());
+/// ```
+pub fn brewer(name: &str, classes: usize) -> Option<Vec<Color>> {
+    PALETTES.iter()
+        .find(|e| e.name.eq_ignore_ascii_case(name) && e.colors.len() == classes)
+        .map(|e| e.colors.clone())
+}
+
+/// Names of the ColorBrewer palettes of a given type
+///
+/// ```
+/// use tint::PaletteType;
+/// let names = tint::brewer_palettes(PaletteType::Diverging);
+/// assert!(names.contains(&"RdBu".to_string()));
+/// ```
+pub fn brewer_palettes(kind: PaletteType) -> Vec<String> {
+    let mut names : Vec<String> = vec![];
+    for e in PALETTES.iter().filter(|e| e.kind == kind) {
+        if !names.contains(&e.name) {
+            names.push(e.name.clone());
+        }
+    }
+    names
+}
+
+/// Smallest and largest number of classes available for a palette
+///
+/// ```
+/// assert_eq!(tint::brewer_classes("Set3"), Some((3, 12)));
+/// assert_eq!(tint::brewer_classes("Nope"), None);
+/// ```
+pub fn brewer_classes(name: &str) -> Option<(usize, usize)> {
+    let n : Vec<usize> = PALETTES.iter()
+        .filter(|e| e.name.eq_ignore_ascii_case(name))
+        .map(|e| e.colors.len())
+        .collect();
+    Some((*n.iter().min()?, *n.iter().max()?))
+}
+
+fn read_brewer(data: &str) -> Vec<Entry> {
+    data.lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut words = line.split_whitespace();
+            let name = words.next()?.to_string();
+            let kind = match words.next()? {
+                "sequential" => PaletteType::Sequential,
+                "diverging" => PaletteType::Diverging,
+                "qualitative" => PaletteType::Qualitative,
+                _ => return None,
+            };
+            let _classes = words.next()?;
+            let colors = words.map(|hex| Color::try_from_hex(hex).ok())
+                .collect::<Option<Vec<Color>>>()?;
+            Some(Entry { name, kind, colors })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn palettes() {
+        assert_eq!(PALETTES.len(), 265);
+        assert_eq!(brewer_palettes(PaletteType::Sequential).len(), 18);
+        assert_eq!(brewer_palettes(PaletteType::Diverging).len(), 9);
+        assert_eq!(brewer_palettes(PaletteType::Qualitative).len(), 8);
+        for e in PALETTES.iter() {
+            let n = e.colors.len();
+            assert_eq!(brewer(&e.name, n).unwrap().len(), n);
+        }
+        assert_eq!(brewer_classes("RdBu"), Some((3, 11)));
+    }
+}
@@ -0,0 +1,78 @@
+//! Single precision color storage
+
+use Color;
+
+/// Color value stored as f32 components
+///
+///   Half the size of Color, intended for passing colors to GPUs and
+///   other single precision APIs.  Color operations are performed on
+///   Color, converting with From/Into.
+///
+/// ```
+/// use tint::{Color, ColorF32};
+/// let c = ColorF32::from(Color::from_rgb255(255, 0, 255));
+/// assert_eq!(c.to_array(), [1.0, 0.0, 1.0, 1.0]);
+/// let back : Color = c.into();
+/// assert_eq!(back.to_hex(), "#ff00ff");
+/// ```
+#[derive(Debug,Copy,Clone,PartialEq)]
+pub struct ColorF32 {
+    /// Red component [0,1]
+    pub red: f32,
+    /// Green component [0,1]
+    pub green: f32,
+    /// Blue component [0,1]
+    pub blue: f32,
+    /// Alpha component [0,1]
+    pub alpha: f32,
+}
+
+impl ColorF32 {
+    /// Create new ColorF32 from red, green, blue and alpha values
+    pub fn new(red: f32, green: f32, blue: f32, alpha: f32) -> ColorF32 {
+        ColorF32 { red, green, blue, alpha }
+    }
+    /// Convert into an array of red, green, blue and alpha
+    pub fn to_array(self) -> [f32;4] {
+        [self.red, self.green, self.blue, self.alpha]
+    }
+}
+
+impl From<Color> for ColorF32 {
+    fn from(c: Color) -> ColorF32 {
+        ColorF32::new(c.red as f32, c.green as f32, c.blue as f32, c.alpha as f32)
+    }
+}
+
+impl From<ColorF32> for Color {
+    fn from(c: ColorF32) -> Color {
+        Color::new(c.red as f64, c.green as f64, c.blue as f64, c.alpha as f64)
+    }
+}
+
+impl From<[f32;4]> for ColorF32 {
+    fn from(c: [f32;4]) -> ColorF32 {
+        ColorF32::new(c[0], c[1], c[2], c[3])
+    }
+}
+
+impl From<ColorF32> for [f32;4] {
+    fn from(c: ColorF32) -> [f32;4] {
+        c.to_array()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn round_trip() {
+        let c = Color::new(0.1, 0.2, 0.3, 0.4);
+        let c32 = ColorF32::from(c);
+        assert_eq!(c32, ColorF32::new(0.1, 0.2, 0.3, 0.4));
+        let back = Color::from(c32);
+        assert!((back.red - c.red).abs() < 1e-7);
+        assert_eq!(back.to_hex_rgba(), c.to_hex_rgba());
+        assert_eq!(ColorF32::from(c32.to_array()), c32);
+    }
+}
@@ -0,0 +1,67 @@
+//! Quantized 8-bit color storage
+
+use Color;
+
+/// Color value quantized to u8 components
+///
+///   Implements Eq, Hash and Ord, so colors can be used as HashMap keys
+///   or stored in a BTreeSet.  Ordering is by red, green, blue then alpha.
+///
+/// ```
+/// use std::collections::HashSet;
+/// use tint::{Color, Color8};
+/// let mut seen = HashSet::new();
+/// seen.insert(Color8::from(Color::from_hex("#c0ffee")));
+/// assert!(seen.contains(&Color8::from(Color::from_rgb255(192, 255, 238))));
+/// ```
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Hash,PartialOrd,Ord)]
+pub struct Color8 {
+    /// Red component [0,255]
+    pub red: u8,
+    /// Green component [0,255]
+    pub green: u8,
+    /// Blue component [0,255]
+    pub blue: u8,
+    /// Alpha component [0,255]
+    pub alpha: u8,
+}
+
+impl Color8 {
+    /// Create new Color8 from red, green, blue and alpha values
+    pub fn new(red: u8, green: u8, blue: u8, alpha: u8) -> Color8 {
+        Color8 { red, green, blue, alpha }
+    }
+}
+
+impl From<Color> for Color8 {
+    fn from(c: Color) -> Color8 {
+        let (r,g,b) = c.to_rgb255();
+        Color8::new(r, g, b, (c.alpha * 255.0) as u8)
+    }
+}
+
+impl From<Color8> for Color {
+    fn from(c: Color8) -> Color {
+        Color::new(c.red as f64 / 255.0, c.green as f64 / 255.0,
+                   c.blue as f64 / 255.0, c.alpha as f64 / 255.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+    #[test]
+    fn quantize() {
+        let c = Color::new(0.5, 0.25, 1.0, 1.0);
+        let k = Color8::from(c);
+        assert_eq!(k, Color8::new(127, 63, 255, 255));
+        assert_eq!(Color8::from(Color::from(k)), k);
+        let set : BTreeSet<Color8> = ["red", "blue", "red", "lime"].iter()
+            .map(|&n| Color8::from(Color::from(n)))
+            .collect();
+        let v : Vec<_> = set.into_iter().collect();
+        assert_eq!(v, vec![Color8::new(0, 0, 255, 255), Color8::new(0, 255, 0, 255),
+                           Color8::new(255, 0, 0, 255)]);
+    }
+}
@@ -0,0 +1,24 @@
+//! Conversion to colored colors, with the "colored" feature
+
+use colored::Color as ColoredColor;
+use Color;
+
+impl From<Color> for ColoredColor {
+    /// Truecolor colored color, alpha is ignored
+    ///
+    /// ```
+    /// # extern crate colored;
+    /// # extern crate tint;
+    /// # use tint::Color;
+    /// # use colored::Colorize;
+    /// # fn main() {
+    /// let c = colored::Color::from(Color::from("#ff8000"));
+    /// assert_eq!(c, colored::Color::TrueColor { r: 255, g: 128, b: 0 });
+    /// println!("{}", "warning".color(c));
+    /// # }
+    /// ```
+    fn from(c: Color) -> ColoredColor {
+        let (r, g, b) = c.to_rgb255();
+        ColoredColor::TrueColor { r, g, b }
+    }
+}
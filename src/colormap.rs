@@ -0,0 +1,328 @@
+//! Colormaps for data visualization
+//!
+//! Perceptually uniform colormaps from matplotlib,
+//! https://bids.github.io/colormap/, and Google's Turbo,
+//! an improved replacement for jet
+
+use Color;
+use Gradient;
+use ParseColorError;
+
+static VIRIDIS: &str = include_str!("colormaps/viridis.txt");
+static MAGMA:   &str = include_str!("colormaps/magma.txt");
+static PLASMA:  &str = include_str!("colormaps/plasma.txt");
+static INFERNO: &str = include_str!("colormaps/inferno.txt");
+static CIVIDIS: &str = include_str!("colormaps/cividis.txt");
+static TURBO:   &str = include_str!("colormaps/turbo.txt");
+
+/// Get a colormap as a Gradient over [0, 1], ignoring case
+///
+///   Available colormaps are listed by colormaps()
+///
+/// ```
+/// let viridis = tint::colormap("viridis").unwrap();
+/// assert_eq!(viridis.sample(0.0).to_hex(), "#440154");
+/// assert_eq!(viridis.sample(1.0).to_hex(), "#fde724");
+/// assert!(tint::colormap("jet").is_none());
+/// ```
+pub fn colormap(name: &str) -> Option<Gradient> {
+    let data = match name.to_lowercase().as_str() {
+        "viridis" => VIRIDIS,
+        "magma"   => MAGMA,
+        "plasma"  => PLASMA,
+        "inferno" => INFERNO,
+        "cividis" => CIVIDIS,
+        "turbo"   => TURBO,
+        _ => return None,
+    };
+    Some(Gradient::from_colors(&read_colormap(data)))
+}
+
+/// Names of the available colormaps
+pub fn colormaps() -> &'static [&'static str] {
+    &["viridis", "magma", "plasma", "inferno", "cividis", "turbo"]
+}
+
+/// Import a colormap exported from matplotlib as JSON
+///
+///   Accepts a list of [r, g, b] or [r, g, b, a] values in [0, 1],
+///   as from `cmap(np.linspace(0, 1, n)).tolist()` or
+///   `ListedColormap.colors`, or the segment data of a
+///   LinearSegmentedColormap, `{"red": [[x, y0, y1], ...], ...}`
+///
+/// ```
+/// let g = tint::colormap_from_json("[[0, 0, 0], [1, 0.5, 0], [1, 1, 1]]").unwrap();
+/// assert_eq!(g.sample(0.5).to_hex(), "#ff7f00");
+///
+/// let seg = r#"{"red":   [[0, 0, 0], [1, 1, 1]],
+///               "green": [[0, 0, 0], [0.5, 0, 1], [1, 1, 1]],
+///               "blue":  [[0, 0, 0], [1, 0, 0]]}"#;
+/// let g = tint::colormap_from_json(seg).unwrap();
+/// assert_eq!(g.sample(0.25).to_hex(), "#3f0000");
+/// assert_eq!(g.sample(0.75).to_hex(), "#bfff00");
+/// ```
+pub fn colormap_from_json(json: &str) -> Result<Gradient, ParseColorError> {
+    if json.trim().is_empty() {
+        return Err(ParseColorError::Empty);
+    }
+    let mut p = JsonParser { s: json.as_bytes(), pos: 0, depth: 0 };
+    let value = p.value()?;
+    p.skip_ws();
+    if p.pos != p.s.len() {
+        return Err(ParseColorError::InvalidSyntax);
+    }
+    match value {
+        Json::Arr(ref rows) => {
+            let colors = rows.iter().map(|row| {
+                let v = numbers(row)?;
+                match v.len() {
+                    3 => Ok(Color::from_rgb1(v[0], v[1], v[2])),
+                    4 => Ok(Color::new(v[0], v[1], v[2], v[3])),
+                    n => Err(ParseColorError::InvalidArgumentCount(n)),
+                }
+            }).collect::<Result<Vec<Color>, ParseColorError>>()?;
+            if colors.is_empty() {
+                return Err(ParseColorError::Empty);
+            }
+            Ok(Gradient::from_colors(&colors))
+        }
+        Json::Obj(ref fields) => segment_data(fields),
+        Json::Num(_) => Err(ParseColorError::InvalidSyntax),
+    }
+}
+
+/// Build a gradient from LinearSegmentedColormap segment data
+fn segment_data(fields: &[(String, Json)]) -> Result<Gradient, ParseColorError> {
+    let channel = |name: &str| -> Result<Option<Vec<[f64;3]>>, ParseColorError> {
+        let rows = match fields.iter().find(|f| f.0 == name) {
+            Some(&(_, Json::Arr(ref rows))) => rows,
+            Some(_) => return Err(ParseColorError::InvalidSyntax),
+            None => return Ok(None),
+        };
+        let mut pts = vec![];
+        for row in rows {
+            let v = numbers(row)?;
+            if v.len() != 3 {
+                return Err(ParseColorError::InvalidArgumentCount(v.len()));
+            }
+            pts.push([v[0], v[1], v[2]]);
+        }
+        if pts.is_empty() {
+            return Err(ParseColorError::Empty);
+        }
+        Ok(Some(pts))
+    };
+    let red = channel("red")?.ok_or(ParseColorError::InvalidSyntax)?;
+    let green = channel("green")?.ok_or(ParseColorError::InvalidSyntax)?;
+    let blue = channel("blue")?.ok_or(ParseColorError::InvalidSyntax)?;
+    let alpha = channel("alpha")?.unwrap_or_else(|| vec![[0.0, 1.0, 1.0], [1.0, 1.0, 1.0]]);
+    let chans = [&red, &green, &blue, &alpha];
+    let mut xs : Vec<f64> = chans.iter().flat_map(|c| c.iter().map(|p| p[0])).collect();
+    xs.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    xs.dedup();
+    let mut stops = vec![];
+    for &x in xs.iter() {
+        let left : Vec<f64> = chans.iter().map(|c| segment_eval(c, x, false)).collect();
+        let right : Vec<f64> = chans.iter().map(|c| segment_eval(c, x, true)).collect();
+        stops.push((x, Color::new(left[0], left[1], left[2], left[3])));
+        if left != right {
+            stops.push((x, Color::new(right[0], right[1], right[2], right[3])));
+        }
+    }
+    Ok(Gradient::new(stops))
+}
+
+/// Value of one segment data channel at x, approached from the left or right
+fn segment_eval(pts: &[[f64;3]], x: f64, right: bool) -> f64 {
+    if let Some(p) = pts.iter().find(|p| p[0] == x) {
+        return if right { p[2] } else { p[1] };
+    }
+    if x < pts[0][0] {
+        return pts[0][1];
+    }
+    for w in pts.windows(2) {
+        if x < w[1][0] {
+            let f = (x - w[0][0]) / (w[1][0] - w[0][0]);
+            return w[0][2] + (w[1][1] - w[0][2]) * f;
+        }
+    }
+    pts[pts.len()-1][2]
+}
+
+fn numbers(v: &Json) -> Result<Vec<f64>, ParseColorError> {
+    match *v {
+        Json::Arr(ref items) => items.iter().map(|x| match *x {
+            Json::Num(n) => Ok(n),
+            _ => Err(ParseColorError::InvalidSyntax),
+        }).collect(),
+        _ => Err(ParseColorError::InvalidSyntax),
+    }
+}
+
+/// The subset of JSON used by colormap data: numbers, arrays and objects
+enum Json {
+    Num(f64),
+    Arr(Vec<Json>),
+    Obj(Vec<(String, Json)>),
+}
+
+/// Deepest nesting of arrays and objects in colormap data
+const MAX_DEPTH: usize = 3;
+
+struct JsonParser<'a> {
+    s: &'a [u8],
+    pos: usize,
+    depth: usize,
+}
+
+impl<'a> JsonParser<'a> {
+    fn skip_ws(&mut self) {
+        while self.pos < self.s.len() && self.s[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+    }
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_ws();
+        self.s.get(self.pos).cloned()
+    }
+    fn expect(&mut self, c: u8) -> Result<(), ParseColorError> {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(ParseColorError::InvalidSyntax)
+        }
+    }
+    fn value(&mut self) -> Result<Json, ParseColorError> {
+        match self.peek() {
+            Some(b'[') | Some(b'{') => {
+                if self.depth == MAX_DEPTH {
+                    return Err(ParseColorError::InvalidSyntax);
+                }
+                self.depth += 1;
+                let v = self.container();
+                self.depth -= 1;
+                v
+            }
+            Some(_) => self.number(),
+            None => Err(ParseColorError::InvalidSyntax),
+        }
+    }
+    fn container(&mut self) -> Result<Json, ParseColorError> {
+        match self.peek() {
+            Some(b'[') => {
+                self.pos += 1;
+                let mut items = vec![];
+                if self.peek() == Some(b']') {
+                    self.pos += 1;
+                    return Ok(Json::Arr(items));
+                }
+                loop {
+                    items.push(self.value()?);
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        Some(b']') => { self.pos += 1; return Ok(Json::Arr(items)); }
+                        _ => return Err(ParseColorError::InvalidSyntax),
+                    }
+                }
+            }
+            Some(b'{') => {
+                self.pos += 1;
+                let mut fields = vec![];
+                if self.peek() == Some(b'}') {
+                    self.pos += 1;
+                    return Ok(Json::Obj(fields));
+                }
+                loop {
+                    let key = self.string()?;
+                    self.expect(b':')?;
+                    fields.push((key, self.value()?));
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        Some(b'}') => { self.pos += 1; return Ok(Json::Obj(fields)); }
+                        _ => return Err(ParseColorError::InvalidSyntax),
+                    }
+                }
+            }
+            _ => Err(ParseColorError::InvalidSyntax),
+        }
+    }
+    fn string(&mut self) -> Result<String, ParseColorError> {
+        self.expect(b'"')?;
+        let start = self.pos;
+        while self.pos < self.s.len() && self.s[self.pos] != b'"' {
+            self.pos += 1;
+        }
+        if self.pos == self.s.len() {
+            return Err(ParseColorError::InvalidSyntax);
+        }
+        let key = String::from_utf8_lossy(&self.s[start..self.pos]).into_owned();
+        self.pos += 1;
+        Ok(key)
+    }
+    fn number(&mut self) -> Result<Json, ParseColorError> {
+        let start = self.pos;
+        while self.pos < self.s.len() &&
+            (self.s[self.pos].is_ascii_alphanumeric() || b"+-.".contains(&self.s[self.pos])) {
+            self.pos += 1;
+        }
+        let text = String::from_utf8_lossy(&self.s[start..self.pos]).into_owned();
+        match text.parse::<f64>() {
+            Ok(v) if v.is_finite() => Ok(Json::Num(v)),
+            _ => Err(ParseColorError::InvalidNumber(text)),
+        }
+    }
+}
+
+fn read_colormap(data: &str) -> Vec<Color> {
+    data.lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let v : Vec<f64> = line.split_whitespace()
+                .filter_map(|x| x.parse().ok())
+                .collect();
+            if v.len() == 3 {
+                Some(Color::from_rgb1(v[0], v[1], v[2]))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn tables() {
+        for name in colormaps() {
+            let g = colormap(name).unwrap();
+            assert_eq!(g.stops().len(), 256, "{}", name);
+        }
+        let magma = colormap("Magma").unwrap();
+        assert_eq!(magma.sample(0.5).to_hex(), "#b53679");
+        let turbo = colormap("turbo").unwrap();
+        assert_eq!(turbo.sample(0.0).to_hex(), "#30123b");
+        assert_eq!(turbo.sample(1.0).to_hex(), "#7a0402");
+    }
+    #[test]
+    fn json() {
+        let g = colormap_from_json("[[0, 0, 0, 0.5], [1, 1, 1, 1]]").unwrap();
+        assert_eq!(g.sample(0.0), Color::new(0.0, 0.0, 0.0, 0.5));
+        let seg = r#"{"red": [[0, 0, 0], [0.5, 0, 1], [1, 1, 1]],
+                      "green": [[0, 0, 0], [1, 0, 0]], "blue": [[0, 0, 0], [1, 0, 0]]}"#;
+        let g = colormap_from_json(seg).unwrap();
+        assert_eq!(g.stops().len(), 4);
+        assert_eq!(g.sample(0.49).red, 0.0);
+        assert_eq!(g.sample(0.51).red, 1.0);
+        assert_eq!(colormap_from_json(""), Err(ParseColorError::Empty));
+        assert_eq!(colormap_from_json("[]"), Err(ParseColorError::Empty));
+        assert_eq!(colormap_from_json("[[0, 0]]"), Err(ParseColorError::InvalidArgumentCount(2)));
+        assert_eq!(colormap_from_json("[[0, 0, x]]"), Err(ParseColorError::InvalidNumber("x".into())));
+        assert_eq!(colormap_from_json("[[0, 0, 0]"), Err(ParseColorError::InvalidSyntax));
+        assert_eq!(colormap_from_json("[[0, 0, 0],"), Err(ParseColorError::InvalidSyntax));
+        assert_eq!(colormap_from_json("{\"red\": [[0, 0, 0]]}"), Err(ParseColorError::InvalidSyntax));
+        assert_eq!(colormap_from_json("{\"red\": [[[0]]]}"), Err(ParseColorError::InvalidSyntax));
+        assert_eq!(colormap_from_json(&"[".repeat(200000)), Err(ParseColorError::InvalidSyntax));
+    }
+}
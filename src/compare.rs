@@ -0,0 +1,89 @@
+//! Approximate color comparison
+
+use Color;
+
+impl Color {
+    /// Check if two colors are equal within an absolute tolerance
+    ///
+    ///   Each of red, green, blue and alpha must differ by at most epsilon
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let c = Color::from_rgb255(250, 202, 222);
+    /// let (h, s, v) = c.to_hsv();
+    /// let c2 = Color::from_hsv(h, s, v);
+    /// assert!(c.approx_eq(&c2, 1e-12));
+    /// assert!(!c.approx_eq(&Color::from_rgb255(250, 202, 223), 1e-3));
+    /// ```
+    pub fn approx_eq(&self, other: &Color, epsilon: f64) -> bool {
+        (self.red   - other.red).abs()   <= epsilon &&
+        (self.green - other.green).abs() <= epsilon &&
+        (self.blue  - other.blue).abs()  <= epsilon &&
+        (self.alpha - other.alpha).abs() <= epsilon
+    }
+}
+
+#[cfg(feature = "approx")]
+mod approx_impls {
+    use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+    use Color;
+
+    impl AbsDiffEq for Color {
+        type Epsilon = f64;
+        fn default_epsilon() -> f64 {
+            f64::default_epsilon()
+        }
+        fn abs_diff_eq(&self, other: &Color, epsilon: f64) -> bool {
+            self.red.abs_diff_eq(&other.red, epsilon) &&
+                self.green.abs_diff_eq(&other.green, epsilon) &&
+                self.blue.abs_diff_eq(&other.blue, epsilon) &&
+                self.alpha.abs_diff_eq(&other.alpha, epsilon)
+        }
+    }
+
+    impl RelativeEq for Color {
+        fn default_max_relative() -> f64 {
+            f64::default_max_relative()
+        }
+        fn relative_eq(&self, other: &Color, epsilon: f64, max_relative: f64) -> bool {
+            self.red.relative_eq(&other.red, epsilon, max_relative) &&
+                self.green.relative_eq(&other.green, epsilon, max_relative) &&
+                self.blue.relative_eq(&other.blue, epsilon, max_relative) &&
+                self.alpha.relative_eq(&other.alpha, epsilon, max_relative)
+        }
+    }
+
+    impl UlpsEq for Color {
+        fn default_max_ulps() -> u32 {
+            f64::default_max_ulps()
+        }
+        fn ulps_eq(&self, other: &Color, epsilon: f64, max_ulps: u32) -> bool {
+            self.red.ulps_eq(&other.red, epsilon, max_ulps) &&
+                self.green.ulps_eq(&other.green, epsilon, max_ulps) &&
+                self.blue.ulps_eq(&other.blue, epsilon, max_ulps) &&
+                self.alpha.ulps_eq(&other.alpha, epsilon, max_ulps)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn approx() {
+        let a = Color::new(0.1, 0.2, 0.3, 1.0);
+        let b = Color::new(0.1 + 1e-9, 0.2, 0.3 - 1e-9, 1.0);
+        assert!(a.approx_eq(&b, 1e-8));
+        assert!(!a.approx_eq(&b, 1e-10));
+        assert!(!a.approx_eq(&Color::new(0.1, 0.2, 0.3, 0.5), 0.1));
+    }
+    #[cfg(feature = "approx")]
+    #[test]
+    fn approx_crate() {
+        let a = Color::new(0.1, 0.2, 0.3, 1.0);
+        let b = Color::new(0.1 + 1e-9, 0.2, 0.3, 1.0);
+        assert_abs_diff_eq!(a, b, epsilon = 1e-8);
+        assert_relative_ne!(a, b);
+        assert_ulps_eq!(a, a);
+    }
+}
@@ -0,0 +1,231 @@
+//! Porter-Duff alpha compositing
+//!
+//! https://www.w3.org/TR/compositing-1/#porterduffcompositingoperators
+
+use Color;
+
+/// Representation of the color components with respect to alpha
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Hash,Default)]
+pub enum AlphaMode {
+    /// Components are independent of alpha, the default
+    #[default]
+    Straight,
+    /// Components have been multiplied by alpha, as is usual for GPUs and video
+    Premultiplied,
+}
+
+impl AlphaMode {
+    /// Convert a Color in this representation to straight alpha
+    pub(crate) fn decode(&self, c: &Color) -> Color {
+        match *self {
+            AlphaMode::Straight => *c,
+            AlphaMode::Premultiplied => Color::from_premultiplied(c.red, c.green, c.blue, c.alpha),
+        }
+    }
+    /// Convert a straight alpha Color to this representation
+    pub(crate) fn encode(&self, c: &Color) -> Color {
+        match *self {
+            AlphaMode::Straight => *c,
+            AlphaMode::Premultiplied => c.to_premultiplied(),
+        }
+    }
+}
+
+/// Porter-Duff compositing operator used by Color::composite()
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Hash,Default)]
+pub enum CompositeOp {
+    /// Neither source nor backdrop
+    Clear,
+    /// Source only
+    Copy,
+    /// Backdrop only
+    Destination,
+    /// Source over backdrop, the default
+    #[default]
+    SourceOver,
+    /// Backdrop over source
+    DestinationOver,
+    /// Source where the backdrop is
+    SourceIn,
+    /// Backdrop where the source is
+    DestinationIn,
+    /// Source where the backdrop is not
+    SourceOut,
+    /// Backdrop where the source is not
+    DestinationOut,
+    /// Source over backdrop, only where the backdrop is
+    SourceAtop,
+    /// Backdrop over source, only where the source is
+    DestinationAtop,
+    /// Source and backdrop where the other is not
+    Xor,
+    /// Sum of source and backdrop, clipped
+    Lighter,
+}
+
+impl CompositeOp {
+    /// All compositing operators
+    pub fn all() -> Vec<CompositeOp> {
+        use self::CompositeOp::*;
+        vec![Clear, Copy, Destination, SourceOver, DestinationOver, SourceIn,
+             DestinationIn, SourceOut, DestinationOut, SourceAtop,
+             DestinationAtop, Xor, Lighter]
+    }
+    /// Fractions of source and backdrop, Fa and Fb
+    fn fractions(&self, a_s: f64, a_b: f64) -> (f64, f64) {
+        match *self {
+            CompositeOp::Clear => (0.0, 0.0),
+            CompositeOp::Copy => (1.0, 0.0),
+            CompositeOp::Destination => (0.0, 1.0),
+            CompositeOp::SourceOver => (1.0, 1.0 - a_s),
+            CompositeOp::DestinationOver => (1.0 - a_b, 1.0),
+            CompositeOp::SourceIn => (a_b, 0.0),
+            CompositeOp::DestinationIn => (0.0, a_s),
+            CompositeOp::SourceOut => (1.0 - a_b, 0.0),
+            CompositeOp::DestinationOut => (0.0, 1.0 - a_s),
+            CompositeOp::SourceAtop => (a_b, 1.0 - a_s),
+            CompositeOp::DestinationAtop => (1.0 - a_b, a_s),
+            CompositeOp::Xor => (1.0 - a_b, 1.0 - a_s),
+            CompositeOp::Lighter => (1.0, 1.0),
+        }
+    }
+}
+
+impl Color {
+    /// Composite the Color (source) with a backdrop using a Porter-Duff operator
+    ///
+    ///   Colors are straight (not premultiplied) alpha.  A fully
+    ///   transparent result is returned as transparent black
+    ///
+    /// ```
+    /// # use tint::{Color, CompositeOp};
+    /// let red = Color::new(1.0, 0.0, 0.0, 0.5);
+    /// let blue = Color::new(0.0, 0.0, 1.0, 1.0);
+    /// assert_eq!(red.composite(&blue, CompositeOp::SourceIn), red);
+    /// assert_eq!(red.composite(&blue, CompositeOp::DestinationOut), Color::new(0.0, 0.0, 1.0, 0.5));
+    /// ```
+    pub fn composite(&self, backdrop: &Color, op: CompositeOp) -> Color {
+        let (a_s, a_b) = (self.alpha, backdrop.alpha);
+        let (fa, fb) = op.fractions(a_s, a_b);
+        let a_o = (a_s * fa + a_b * fb).min(1.0);
+        if a_o <= 0.0 {
+            return Color::new(0.0, 0.0, 0.0, 0.0);
+        }
+        let f = |cs: f64, cb: f64| ((a_s * fa * cs + a_b * fb * cb) / a_o).min(1.0);
+        Color::new(f(self.red, backdrop.red),
+                   f(self.green, backdrop.green),
+                   f(self.blue, backdrop.blue),
+                   a_o)
+    }
+    /// Composite the Color over a backdrop, Porter-Duff source-over
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let red = Color::new(1.0, 0.0, 0.0, 0.5);
+    /// let blue = Color::new(0.0, 0.0, 1.0, 1.0);
+    /// assert_eq!(red.over(&blue), Color::new(0.5, 0.0, 0.5, 1.0));
+    /// ```
+    pub fn over(&self, backdrop: &Color) -> Color {
+        self.composite(backdrop, CompositeOp::SourceOver)
+    }
+    /// Composite like composite(), with colors in the given alpha representation
+    ///
+    ///   Both inputs and the result use the same representation
+    ///
+    /// ```
+    /// # use tint::{AlphaMode, Color, CompositeOp};
+    /// let red = Color::new(0.5, 0.0, 0.0, 0.5);
+    /// let blue = Color::new(0.0, 0.0, 1.0, 1.0);
+    /// let c = red.composite_as(&blue, CompositeOp::SourceOver, AlphaMode::Premultiplied);
+    /// assert_eq!(c, Color::new(0.5, 0.0, 0.5, 1.0));
+    /// ```
+    pub fn composite_as(&self, backdrop: &Color, op: CompositeOp, alpha: AlphaMode) -> Color {
+        let c = alpha.decode(self).composite(&alpha.decode(backdrop), op);
+        alpha.encode(&c)
+    }
+    /// Convert to premultiplied alpha, each component multiplied by alpha
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let c = Color::new(1.0, 0.5, 0.0, 0.5);
+    /// assert_eq!(c.to_premultiplied(), Color::new(0.5, 0.25, 0.0, 0.5));
+    /// ```
+    pub fn to_premultiplied(&self) -> Color {
+        Color::new(self.red * self.alpha, self.green * self.alpha,
+                   self.blue * self.alpha, self.alpha)
+    }
+    /// Create new Color from premultiplied alpha components
+    ///
+    ///   Components are divided by alpha; a zero alpha gives transparent black
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let c = Color::from_premultiplied(0.5, 0.25, 0.0, 0.5);
+    /// assert_eq!(c, Color::new(1.0, 0.5, 0.0, 0.5));
+    /// ```
+    pub fn from_premultiplied(red: f64, green: f64, blue: f64, alpha: f64) -> Color {
+        if alpha <= 0.0 {
+            return Color::new(0.0, 0.0, 0.0, 0.0);
+        }
+        Color::new(red / alpha, green / alpha, blue / alpha, alpha)
+    }
+}
+
+/// Composite a stack of colors over an opaque background
+///
+///   Layers are ordered from bottom to top, each composited
+///   source-over the result so far.  The background is treated as
+///   opaque, so the result is always opaque
+///
+/// ```
+/// # use tint::{flatten, Color};
+/// let white = Color::new(1.0, 1.0, 1.0, 1.0);
+/// let layers = [Color::new(0.0, 0.0, 0.0, 0.5), Color::new(1.0, 0.0, 0.0, 0.5)];
+/// assert_eq!(flatten(&layers, &white), Color::new(0.75, 0.25, 0.25, 1.0));
+/// ```
+pub fn flatten(layers: &[Color], background: &Color) -> Color {
+    layers.iter().fold(background.with_alpha(1.0), |acc, c| c.over(&acc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn operators() {
+        let src = Color::new(0.2, 0.4, 0.6, 0.5);
+        let dst = Color::new(0.7, 0.5, 0.3, 0.8);
+        assert_eq!(src.composite(&dst, CompositeOp::Clear), Color::new(0.0, 0.0, 0.0, 0.0));
+        assert_eq!(src.composite(&dst, CompositeOp::Copy), src);
+        assert!(src.composite(&dst, CompositeOp::Destination).approx_eq(&dst, 1e-12));
+        assert!(src.composite(&dst, CompositeOp::DestinationOver)
+                .approx_eq(&dst.over(&src), 1e-12));
+        assert!((src.over(&dst).alpha - 0.9).abs() < 1e-12);
+        assert!((src.composite(&dst, CompositeOp::Xor).alpha - 0.5).abs() < 1e-12);
+        for op in CompositeOp::all() {
+            let c = src.composite(&dst, op);
+            assert!(c.channels().all(|v| (0.0..=1.0).contains(&v)), "{:?}", op);
+        }
+    }
+    #[test]
+    fn premultiplied() {
+        let src = Color::new(0.2, 0.4, 0.6, 0.5);
+        let dst = Color::new(0.7, 0.5, 0.3, 0.8);
+        let p = src.to_premultiplied();
+        assert!(Color::from_premultiplied(p.red, p.green, p.blue, p.alpha).approx_eq(&src, 1e-12));
+        assert_eq!(Color::new(0.0, 0.0, 0.0, 0.0).to_premultiplied().alpha, 0.0);
+        for op in CompositeOp::all() {
+            let a = src.composite(&dst, op).to_premultiplied();
+            let b = p.composite_as(&dst.to_premultiplied(), op, AlphaMode::Premultiplied);
+            assert!(a.approx_eq(&b, 1e-12), "{:?}", op);
+        }
+    }
+    #[test]
+    fn flat() {
+        let bg = Color::new(0.7, 0.5, 0.3, 0.2);
+        assert_eq!(flatten(&[], &bg), bg.with_alpha(1.0));
+        let src = Color::new(0.2, 0.4, 0.6, 0.5);
+        assert!(flatten(&[src, src], &bg).approx_eq(&src.over(&src.over(&bg.with_alpha(1.0))), 1e-12));
+        let opaque = Color::new(0.1, 0.2, 0.3, 1.0);
+        assert_eq!(flatten(&[src, opaque], &bg), opaque);
+    }
+}
@@ -0,0 +1,307 @@
+//! Accessibility: luminance and contrast
+//!
+//! https://www.w3.org/TR/WCAG21/#dfn-relative-luminance
+//!
+//! https://github.com/Myndex/apca-w3
+
+use adjust::clip;
+use Color;
+
+/// Text size class used by WCAG contrast levels
+///
+///   Large text is at least 18 point, or 14 point bold
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Hash,Default)]
+pub enum TextSize {
+    /// Body text, the default
+    #[default]
+    Normal,
+    /// Large text
+    Large,
+}
+
+impl TextSize {
+    /// Minimum contrast ratios for levels AA and AAA
+    fn thresholds(&self) -> (f64, f64) {
+        match *self {
+            TextSize::Normal => (4.5, 7.0),
+            TextSize::Large => (3.0, 4.5),
+        }
+    }
+}
+
+/// Contrast of one pair of colors, from contrast_matrix()
+#[derive(Debug,Copy,Clone,PartialEq)]
+pub struct ContrastPair {
+    /// WCAG contrast ratio, [1,21]
+    pub ratio: f64,
+    /// Passes level AA for normal text
+    pub aa: bool,
+    /// Passes level AA for large text
+    pub aa_large: bool,
+    /// Passes level AAA for normal text
+    pub aaa: bool,
+    /// Passes level AAA for large text
+    pub aaa_large: bool,
+}
+
+impl ContrastPair {
+    /// Contrast of two colors
+    pub fn new(a: &Color, b: &Color) -> ContrastPair {
+        let ratio = a.contrast_ratio(b);
+        let (aa, aaa) = TextSize::Normal.thresholds();
+        let (aa_large, aaa_large) = TextSize::Large.thresholds();
+        ContrastPair {
+            ratio,
+            aa: ratio >= aa,
+            aa_large: ratio >= aa_large,
+            aaa: ratio >= aaa,
+            aaa_large: ratio >= aaa_large,
+        }
+    }
+    /// Highest level passed: "AAA", "AA", "AA Large" or "Fail"
+    ///
+    ///   AAA for large text has the same threshold as AA, so it is
+    ///   reported as "AA"
+    pub fn level(&self) -> &'static str {
+        if self.aaa {
+            "AAA"
+        } else if self.aa {
+            "AA"
+        } else if self.aa_large {
+            "AA Large"
+        } else {
+            "Fail"
+        }
+    }
+}
+
+/// Pairwise contrast of a palette
+///
+///   Entry [i][j] is the contrast of colors[i] and colors[j]; the
+///   matrix is symmetric with a ratio of 1 on the diagonal
+///
+/// ```
+/// # use tint::{contrast_matrix, Color};
+/// let colors = [Color::from("black"), Color::from("white"), Color::from("gray")];
+/// let m = contrast_matrix(&colors);
+/// assert_eq!(m[0][1].ratio, 21.0);
+/// assert_eq!(m[0][1].level(), "AAA");
+/// assert_eq!(m[1][2].level(), "AA Large");
+/// assert_eq!(m[2][2].level(), "Fail");
+/// ```
+pub fn contrast_matrix(colors: &[Color]) -> Vec<Vec<ContrastPair>> {
+    colors.iter()
+        .map(|a| colors.iter().map(|b| ContrastPair::new(a, b)).collect())
+        .collect()
+}
+
+impl Color {
+    /// WCAG relative luminance, [0,1]
+    ///
+    ///   Components are linearized with the sRGB transfer function;
+    ///   alpha is ignored
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// assert_eq!(Color::from_rgb255(255, 255, 255).luminance(), 1.0);
+    /// assert_eq!(Color::from_rgb255(0, 0, 0).luminance(), 0.0);
+    /// assert!((Color::from_rgb255(255, 0, 0).luminance() - 0.2126).abs() < 1e-12);
+    /// ```
+    pub fn luminance(&self) -> f64 {
+        let (r,g,b) = self.to_linear_rgb();
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    }
+    /// WCAG contrast ratio with another color, [1,21]
+    ///
+    ///   The ratio is symmetric, the lighter color may be either one
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let black = Color::from_rgb255(0, 0, 0);
+    /// let white = Color::from_rgb255(255, 255, 255);
+    /// assert_eq!(black.contrast_ratio(&white), 21.0);
+    /// assert_eq!(white.contrast_ratio(&black), 21.0);
+    /// ```
+    pub fn contrast_ratio(&self, other: &Color) -> f64 {
+        let (a, b) = (self.luminance(), other.luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+    /// Check WCAG level AA contrast against a background
+    ///
+    /// ```
+    /// # use tint::{Color, TextSize};
+    /// let gray = Color::from_rgb255(119, 119, 119);
+    /// let white = Color::from_rgb255(255, 255, 255);
+    /// assert!(!gray.passes_aa(&white, TextSize::Normal));
+    /// assert!(gray.passes_aa(&white, TextSize::Large));
+    /// ```
+    pub fn passes_aa(&self, background: &Color, size: TextSize) -> bool {
+        self.contrast_ratio(background) >= size.thresholds().0
+    }
+    /// Check WCAG level AAA contrast against a background
+    pub fn passes_aaa(&self, background: &Color, size: TextSize) -> bool {
+        self.contrast_ratio(background) >= size.thresholds().1
+    }
+    /// Black or white, whichever has the higher contrast ratio as text on the Color
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// assert_eq!(Color::from("yellow").text_color(), Color::from("black"));
+    /// assert_eq!(Color::from("navy").text_color(), Color::from("white"));
+    /// ```
+    pub fn text_color(&self) -> Color {
+        self.text_color_from(&Color::new(0.0, 0.0, 0.0, 1.0), &Color::new(1.0, 1.0, 1.0, 1.0))
+    }
+    /// The option with the higher contrast ratio as text on the Color
+    ///
+    ///   Ties go to the first option
+    pub fn text_color_from(&self, a: &Color, b: &Color) -> Color {
+        if self.contrast_ratio(b) > self.contrast_ratio(a) { *b } else { *a }
+    }
+    /// Adjust Oklch lightness until the contrast ratio with a background meets a target
+    ///
+    ///   Hue and chroma are kept and lightness is changed as little as
+    ///   possible, trying both lighter and darker.  The result is
+    ///   clipped to the sRGB gamut and alpha is unchanged.  Returns
+    ///   None if the target cannot be met, e.g. targets above 21
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let white = Color::from_rgb255(255, 255, 255);
+    /// let c = Color::from_rgb255(120, 160, 220).ensure_contrast(&white, 4.5).unwrap();
+    /// assert!(c.contrast_ratio(&white) >= 4.5);
+    /// assert!(c.contrast_ratio(&white) < 4.6);
+    /// assert!(Color::from("gray").ensure_contrast(&white, 22.0).is_none());
+    /// ```
+    pub fn ensure_contrast(&self, background: &Color, target: f64) -> Option<Color> {
+        if self.contrast_ratio(background) >= target {
+            return Some(*self);
+        }
+        let (l,c,h) = self.to_oklch();
+        let at = |l: f64| clip(Color::from_oklch(l, c, h), self.alpha);
+        [0.0, 1.0].iter()
+            .filter(|&&end| at(end).contrast_ratio(background) >= target)
+            .map(|&end| {
+                // Bisect between the current lightness (fails) and end (passes)
+                let (mut lo, mut hi) = (l, end);
+                for _ in 0..48 {
+                    let mid = (lo + hi) / 2.0;
+                    if at(mid).contrast_ratio(background) >= target {
+                        hi = mid;
+                    } else {
+                        lo = mid;
+                    }
+                }
+                (hi, at(hi))
+            })
+            .min_by(|a, b| (a.0 - l).abs().partial_cmp(&(b.0 - l).abs()).unwrap())
+            .map(|(_, c)| c)
+    }
+    /// APCA lightness contrast (Lc) of the Color as text on a background
+    ///
+    ///   Uses APCA 0.0.98G-4g, the WCAG 3 draft method.  Unlike
+    ///   contrast_ratio() the result depends on polarity: dark text on a
+    ///   light background is positive, light text on a dark background
+    ///   is negative.  Magnitudes run from 0 to about 108
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let black = Color::from_rgb255(0, 0, 0);
+    /// let white = Color::from_rgb255(255, 255, 255);
+    /// assert!((black.apca_contrast(&white) - 106.04).abs() < 0.01);
+    /// assert!((white.apca_contrast(&black) + 107.88).abs() < 0.01);
+    /// ```
+    pub fn apca_contrast(&self, background: &Color) -> f64 {
+        let text = apca_y(self);
+        let bg = apca_y(background);
+        if (bg - text).abs() < 0.0005 {
+            return 0.0;
+        }
+        let lc = if bg > text {
+            let sapc = (bg.powf(0.56) - text.powf(0.57)) * 1.14;
+            if sapc < 0.1 { 0.0 } else { sapc - 0.027 }
+        } else {
+            let sapc = (bg.powf(0.65) - text.powf(0.62)) * 1.14;
+            if sapc > -0.1 { 0.0 } else { sapc + 0.027 }
+        };
+        lc * 100.0
+    }
+}
+
+/// APCA screen luminance, with soft clamp of near black
+fn apca_y(c: &Color) -> f64 {
+    let f = |v: f64| v.clamp(0.0, 1.0).powf(2.4);
+    let y = 0.2126729 * f(c.red) + 0.7151522 * f(c.green) + 0.0721750 * f(c.blue);
+    if y < 0.022 {
+        y + (0.022 - y).powf(1.414)
+    } else {
+        y
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn luminance() {
+        let gray = Color::from_rgb255(119, 119, 119);
+        assert!((gray.luminance() - 0.1845).abs() < 1e-4);
+        assert_eq!(gray.with_alpha(0.0).luminance(), gray.luminance());
+    }
+    #[test]
+    fn ratio() {
+        let white = Color::from_rgb255(255, 255, 255);
+        let gray = Color::from_rgb255(118, 118, 118);
+        assert!((gray.contrast_ratio(&white) - 4.54).abs() < 0.01);
+        assert_eq!(gray.contrast_ratio(&gray), 1.0);
+        assert!(gray.passes_aa(&white, TextSize::Normal));
+        assert!(!gray.passes_aaa(&white, TextSize::Normal));
+        assert!(gray.passes_aaa(&white, TextSize::Large));
+    }
+    #[test]
+    fn apca() {
+        let white = Color::from_rgb255(255, 255, 255);
+        let gray = Color::from_hex("#888888");
+        assert!((gray.apca_contrast(&white) - 63.06).abs() < 0.01);
+        assert!((white.apca_contrast(&gray) + 68.54).abs() < 0.01);
+        assert_eq!(gray.apca_contrast(&gray), 0.0);
+    }
+    #[test]
+    fn text() {
+        let dark = Color::from_hex("#333333");
+        let light = Color::from_hex("#eeeeee");
+        assert_eq!(Color::from_hex("#777777").text_color(), Color::from("black"));
+        assert_eq!(Color::from_hex("#999999").text_color_from(&dark, &light), dark);
+        assert_eq!(Color::from_hex("#555555").text_color_from(&dark, &light), light);
+        assert_eq!(dark.text_color_from(&light, &light), light);
+    }
+    #[test]
+    fn ensure() {
+        let bg = Color::from_hex("#777777");
+        let c = Color::new(0.5, 0.45, 0.4, 0.5);
+        assert_eq!(c.ensure_contrast(&bg, 1.0), Some(c));
+        for target in [3.0, 4.5] {
+            let d = c.ensure_contrast(&bg, target).unwrap();
+            assert!(d.contrast_ratio(&bg) >= target);
+            assert_eq!(d.alpha, 0.5);
+        }
+        // Only darkening reaches 4.5 against middle gray
+        assert!(c.ensure_contrast(&bg, 4.5).unwrap().luminance() < bg.luminance());
+        assert!(c.ensure_contrast(&bg, 21.0).is_none());
+    }
+    #[test]
+    fn matrix() {
+        assert!(contrast_matrix(&[]).is_empty());
+        let colors = [Color::from("navy"), Color::from("yellow"), Color::from("teal")];
+        let m = contrast_matrix(&colors);
+        assert_eq!(m.len(), 3);
+        for (i, row) in m.iter().enumerate() {
+            assert_eq!(row.len(), 3);
+            assert_eq!(row[i].ratio, 1.0);
+            for (j, pair) in row.iter().enumerate() {
+                assert_eq!(*pair, m[j][i]);
+            }
+        }
+        assert!(m[0][1].aaa && m[0][1].aa_large);
+    }
+}
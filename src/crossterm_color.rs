@@ -0,0 +1,69 @@
+//! Conversions to and from crossterm colors, with the "crossterm" feature
+
+use crossterm::style::Color as TermColor;
+use Color;
+
+impl From<Color> for TermColor {
+    fn from(c: Color) -> TermColor {
+        let (r, g, b) = c.to_rgb255();
+        TermColor::Rgb { r, g, b }
+    }
+}
+
+impl Color {
+    /// Closest xterm 256 indexed crossterm color, for terminals without truecolor
+    ///
+    /// ```
+    /// # extern crate crossterm;
+    /// # extern crate tint;
+    /// # use tint::Color;
+    /// # fn main() {
+    /// let red = Color::from("red");
+    /// assert_eq!(red.to_crossterm_indexed(), crossterm::style::Color::AnsiValue(196));
+    /// # }
+    /// ```
+    pub fn to_crossterm_indexed(&self) -> TermColor {
+        TermColor::AnsiValue(self.to_ansi256())
+    }
+    /// Create new Color from a crossterm color
+    ///   alpha value set to 1.0
+    ///
+    ///   Named and indexed colors use the xterm defaults.  Reset, the
+    ///   terminal default color, returns None
+    pub fn from_crossterm(c: TermColor) -> Option<Color> {
+        let index = match c {
+            TermColor::Reset => return None,
+            TermColor::Rgb { r, g, b } => return Some(Color::from_rgb255(r, g, b)),
+            TermColor::AnsiValue(i) => i,
+            TermColor::Black => 0,
+            TermColor::DarkRed => 1,
+            TermColor::DarkGreen => 2,
+            TermColor::DarkYellow => 3,
+            TermColor::DarkBlue => 4,
+            TermColor::DarkMagenta => 5,
+            TermColor::DarkCyan => 6,
+            TermColor::Grey => 7,
+            TermColor::DarkGrey => 8,
+            TermColor::Red => 9,
+            TermColor::Green => 10,
+            TermColor::Yellow => 11,
+            TermColor::Blue => 12,
+            TermColor::Magenta => 13,
+            TermColor::Cyan => 14,
+            TermColor::White => 15,
+        };
+        Some(Color::from_ansi256(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn crossterm() {
+        let c = Color::from_rgb255(10, 20, 30);
+        assert_eq!(Color::from_crossterm(TermColor::from(c)), Some(c));
+        assert_eq!(Color::from_crossterm(TermColor::Reset), None);
+        assert_eq!(Color::from_crossterm(TermColor::Red), Some(Color::from("red")));
+    }
+}
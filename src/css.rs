@@ -0,0 +1,422 @@
+//! CSS functional color syntax
+//!
+//! https://www.w3.org/TR/css-color-4/
+
+use Color;
+use ColorSpace;
+use ParseColorError;
+use hsl2rgb;
+
+impl Color {
+    /// Create new Color from a CSS functional string
+    ///
+    ///   Supported functions: rgb(), rgba(), hsl(), hsla(), hwb(),
+    ///      lab(), lch(), oklab(), oklch() and color()
+    ///   Arguments may be separated by commas or whitespace, with an
+    ///   optional alpha following a slash: lab(50 20 -30 / 0.5)
+    ///   Channels and alpha may also be given as percentages
+    ///   Hues may be given in deg (default), grad, rad or turn
+    ///   Colors outside of the sRGB gamut are clipped
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let red = Color::from_css("rgb(255, 0, 0)").unwrap();
+    /// assert_eq!(red, Color::from_rgb255(255, 0, 0));
+    /// let red = Color::from_css("rgb(255 0 0)").unwrap();
+    /// assert_eq!(red, Color::from_rgb255(255, 0, 0));
+    /// let red = Color::from_css("rgba(255, 0, 0, 0.5)").unwrap();
+    /// assert_eq!(red, Color::new(1.0, 0.0, 0.0, 0.5));
+    /// let red = Color::from_css("rgb(100% 0% 0% / 50%)").unwrap();
+    /// assert_eq!(red, Color::new(1.0, 0.0, 0.0, 0.5));
+    /// let green = Color::from_css("hsl(120, 100%, 50%)").unwrap();
+    /// assert_eq!(green, Color::from_rgb255(0, 255, 0));
+    /// let green = Color::from_css("hsla(0.3333turn, 100%, 25%, 1.0)").unwrap();
+    /// assert_eq!(green.to_hex(), "#007f00");
+    /// let c = Color::from_css("hwb(194 0% 0% / .5)").unwrap();
+    /// assert_eq!(c.to_hex_rgba(), "#00c3ff7f");
+    /// let c = Color::from_css("oklch(62.8% 0.2577 29.23)").unwrap();
+    /// assert_eq!(c.to_rgb255(), (255, 0, 0));
+    /// let c = Color::from_css("color(display-p3 0.5 0.5 0.5 / 0.25)").unwrap();
+    /// assert_eq!(c.to_hex_rgba(), "#7f7f7f3f");
+    /// ```
+    pub fn from_css(s: &str) -> Result<Color, ParseColorError> {
+        let (name, args) = split_function(s)?;
+        match name.as_str() {
+            "rgb" | "rgba" => parse_rgb(&args),
+            "hsl" | "hsla" => parse_hsl(&args),
+            "hwb" => parse_hwb(&args),
+            "lab" => parse_lab(&args, false),
+            "lch" => parse_lch(&args, false),
+            "oklab" => parse_lab(&args, true),
+            "oklch" => parse_lch(&args, true),
+            "color" => parse_color(&args),
+            _ => Err(ParseColorError::InvalidFunction(name)),
+        }
+    }
+}
+
+impl Color {
+    /// Convert Color into a CSS rgb() string, alpha is ignored
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let facade = Color::from_hex("#facade");
+    /// assert_eq!(facade.to_css_rgb(), "rgb(250 202 222)");
+    /// ```
+    pub fn to_css_rgb(&self) -> String {
+        let (r,g,b) = self.to_rgb255();
+        format!("rgb({} {} {})", r, g, b)
+    }
+    /// Convert Color into a CSS rgb() string including alpha
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let facade = Color::from_hex("#facade");
+    /// assert_eq!(facade.to_css_rgba(), "rgb(250 202 222 / 1)");
+    /// let facade = Color::from_hex("#facade80");
+    /// assert_eq!(facade.to_css_rgba(), "rgb(250 202 222 / 0.502)");
+    /// ```
+    pub fn to_css_rgba(&self) -> String {
+        let (r,g,b) = self.to_rgb255();
+        format!("rgb({} {} {} / {})", r, g, b, fmt_num(self.alpha, 3))
+    }
+    /// Convert Color into a CSS hsl() string
+    ///
+    ///   Alpha is included only if it is less than 1.0
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let facade = Color::from_hex("#facade");
+    /// assert_eq!(facade.to_css_hsl(), "hsl(335 82.76% 88.63%)");
+    /// let red = Color::new(1.0, 0.0, 0.0, 0.5);
+    /// assert_eq!(red.to_css_hsl(), "hsl(0 100% 50% / 0.5)");
+    /// ```
+    pub fn to_css_hsl(&self) -> String {
+        let (h,s,l) = self.to_hsl();
+        let hsl = format!("{} {}% {}%", fmt_num(h, 2),
+                          fmt_num(s * 100.0, 2), fmt_num(l * 100.0, 2));
+        if self.alpha < 1.0 {
+            format!("hsl({} / {})", hsl, fmt_num(self.alpha, 3))
+        } else {
+            format!("hsl({})", hsl)
+        }
+    }
+    /// Convert Color into a CSS color() string in a predefined color space
+    ///
+    ///   Alpha is included only if it is less than 1.0
+    ///
+    /// ```
+    /// # use tint::{Color, ColorSpace};
+    /// let red = Color::from_rgb1(1.0, 0.0, 0.0);
+    /// assert_eq!(red.to_css_color(ColorSpace::DisplayP3), "color(display-p3 0.91749 0.20029 0.13856)");
+    /// let red = Color::new(1.0, 0.0, 0.0, 0.5);
+    /// assert_eq!(red.to_css_color(ColorSpace::Srgb), "color(srgb 1 0 0 / 0.5)");
+    /// ```
+    pub fn to_css_color(&self, space: ColorSpace) -> String {
+        let (c0, c1, c2) = self.to_color_space(space);
+        let c = format!("{} {} {} {}", space.css_name(),
+                        fmt_num(c0, 5), fmt_num(c1, 5), fmt_num(c2, 5));
+        if self.alpha < 1.0 {
+            format!("color({} / {})", c, fmt_num(self.alpha, 3))
+        } else {
+            format!("color({})", c)
+        }
+    }
+}
+
+/// Format a number with at most `decimals` digits after the decimal point,
+///   without trailing zeros
+pub(crate) fn fmt_num(v: f64, decimals: usize) -> String {
+    let s = format!("{:.*}", decimals, v);
+    let s = if s.contains('.') {
+        s.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &s
+    };
+    if s == "-0" { "0".to_string() } else { s.to_string() }
+}
+
+/// Split "name(a, b, c / alpha)" into the lowercase function name and its arguments
+///
+///   Alpha, if present, is returned as the final argument
+fn split_function(s: &str) -> Result<(String, Vec<&str>), ParseColorError> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err(ParseColorError::Empty);
+    }
+    let open = s.find('(').ok_or(ParseColorError::InvalidSyntax)?;
+    if !s.ends_with(')') {
+        return Err(ParseColorError::InvalidSyntax);
+    }
+    let name = s[..open].trim().to_lowercase();
+    let body = &s[open+1..s.len()-1];
+    if body.contains('(') || body.contains(')') {
+        return Err(ParseColorError::InvalidSyntax);
+    }
+    let (body, alpha) = match body.find('/') {
+        Some(i) => (&body[..i], Some(body[i+1..].trim())),
+        None => (body, None),
+    };
+    let mut args : Vec<&str> = if body.contains(',') {
+        if alpha.is_some() {
+            return Err(ParseColorError::InvalidSyntax);
+        }
+        body.split(',').map(|x| x.trim()).collect()
+    } else {
+        body.split_whitespace().collect()
+    };
+    if let Some(a) = alpha {
+        if a.is_empty() || a.contains('/') || a.contains(char::is_whitespace) {
+            return Err(ParseColorError::InvalidSyntax);
+        }
+        args.push(a);
+    }
+    if args.iter().any(|x| x.is_empty()) {
+        return Err(ParseColorError::InvalidSyntax);
+    }
+    Ok((name, args))
+}
+
+fn number(s: &str) -> Result<f64, ParseColorError> {
+    match s.parse::<f64>() {
+        Ok(v) if v.is_finite() => Ok(v),
+        _ => Err(ParseColorError::InvalidNumber(s.to_owned())),
+    }
+}
+
+/// Parse a percentage "50%", or a bare number on the same 0-100 scale
+fn percent(s: &str) -> Result<f64, ParseColorError> {
+    let v = s.strip_suffix('%').unwrap_or(s);
+    Ok(number(v)? / 100.0)
+}
+
+/// Parse an angle and return it in degrees [0, 360)
+fn hue(s: &str) -> Result<f64, ParseColorError> {
+    let s = s.to_lowercase();
+    let units = [("deg", 1.0), ("grad", 360.0 / 400.0),
+                 ("rad", 180.0 / std::f64::consts::PI), ("turn", 360.0)];
+    let mut deg = None;
+    for &(unit, scale) in units.iter() {
+        if let Some(v) = s.strip_suffix(unit) {
+            deg = Some(number(v)? * scale);
+            break;
+        }
+    }
+    let deg = match deg {
+        Some(d) => d,
+        None => number(&s)?,
+    };
+    Ok(deg.rem_euclid(360.0))
+}
+
+/// Parse a number, or a percentage of a reference value
+fn scaled(s: &str, reference: f64) -> Result<f64, ParseColorError> {
+    match s.strip_suffix('%') {
+        Some(v) => Ok(number(v)? * reference / 100.0),
+        None => number(s),
+    }
+}
+
+/// Parse an alpha value as a number [0, 1] or a percentage
+fn alpha(s: &str) -> Result<f64, ParseColorError> {
+    scaled(s, 1.0)
+}
+
+fn clip(c: Color) -> Color {
+    Color::new(clamp1(c.red), clamp1(c.green), clamp1(c.blue), clamp1(c.alpha))
+}
+
+fn clamp1(v: f64) -> f64 {
+    v.clamp(0.0, 1.0)
+}
+
+fn parse_rgb(args: &[&str]) -> Result<Color, ParseColorError> {
+    if args.len() != 3 && args.len() != 4 {
+        return Err(ParseColorError::InvalidArgumentCount(args.len()));
+    }
+    let r = scaled(args[0], 255.0)?;
+    let g = scaled(args[1], 255.0)?;
+    let b = scaled(args[2], 255.0)?;
+    let a = if args.len() == 4 { alpha(args[3])? } else { 1.0 };
+    Ok(Color::new(clamp1(r / 255.0), clamp1(g / 255.0), clamp1(b / 255.0), clamp1(a)))
+}
+
+fn parse_hsl(args: &[&str]) -> Result<Color, ParseColorError> {
+    if args.len() != 3 && args.len() != 4 {
+        return Err(ParseColorError::InvalidArgumentCount(args.len()));
+    }
+    let h = hue(args[0])?;
+    let s = clamp1(percent(args[1])?);
+    let l = clamp1(percent(args[2])?);
+    let a = if args.len() == 4 { alpha(args[3])? } else { 1.0 };
+    let (r,g,b) = hsl2rgb(h / 360.0, s, l);
+    Ok(Color::new(r, g, b, clamp1(a)))
+}
+
+fn parse_hwb(args: &[&str]) -> Result<Color, ParseColorError> {
+    if args.len() != 3 && args.len() != 4 {
+        return Err(ParseColorError::InvalidArgumentCount(args.len()));
+    }
+    let h = hue(args[0])?;
+    let w = clamp1(percent(args[1])?);
+    let b = clamp1(percent(args[2])?);
+    let a = if args.len() == 4 { alpha(args[3])? } else { 1.0 };
+    let mut c = Color::from_hwb(h, w, b);
+    c.alpha = clamp1(a);
+    Ok(c)
+}
+
+fn parse_lab(args: &[&str], ok: bool) -> Result<Color, ParseColorError> {
+    if args.len() != 3 && args.len() != 4 {
+        return Err(ParseColorError::InvalidArgumentCount(args.len()));
+    }
+    let (lref, abref) = if ok { (1.0, 0.4) } else { (100.0, 125.0) };
+    let l = scaled(args[0], lref)?.max(0.0);
+    let a = scaled(args[1], abref)?;
+    let b = scaled(args[2], abref)?;
+    let alpha = if args.len() == 4 { alpha(args[3])? } else { 1.0 };
+    let mut c = if ok { Color::from_oklab(l, a, b) } else { Color::from_lab(l, a, b) };
+    c.alpha = alpha;
+    Ok(clip(c))
+}
+
+fn parse_lch(args: &[&str], ok: bool) -> Result<Color, ParseColorError> {
+    if args.len() != 3 && args.len() != 4 {
+        return Err(ParseColorError::InvalidArgumentCount(args.len()));
+    }
+    let (lref, cref) = if ok { (1.0, 0.4) } else { (100.0, 150.0) };
+    let l = scaled(args[0], lref)?.max(0.0);
+    let c = scaled(args[1], cref)?.max(0.0);
+    let h = hue(args[2])?;
+    let alpha = if args.len() == 4 { alpha(args[3])? } else { 1.0 };
+    let mut c = if ok { Color::from_oklch(l, c, h) } else { Color::from_lch(l, c, h) };
+    c.alpha = alpha;
+    Ok(clip(c))
+}
+
+fn parse_color(args: &[&str]) -> Result<Color, ParseColorError> {
+    if args.len() != 4 && args.len() != 5 {
+        return Err(ParseColorError::InvalidArgumentCount(args.len()));
+    }
+    let space = ColorSpace::from_css_name(args[0])
+        .ok_or_else(|| ParseColorError::InvalidFunction(format!("color({})", args[0])))?;
+    let c0 = scaled(args[1], 1.0)?;
+    let c1 = scaled(args[2], 1.0)?;
+    let c2 = scaled(args[3], 1.0)?;
+    let alpha = if args.len() == 5 { alpha(args[4])? } else { 1.0 };
+    let mut c = Color::from_color_space(space, c0, c1, c2);
+    c.alpha = alpha;
+    Ok(clip(c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(c: Color, hex: &str) {
+        let h = Color::from_hex(hex);
+        assert!((c.red - h.red).abs() < 1.0 / 255.0 &&
+                (c.green - h.green).abs() < 1.0 / 255.0 &&
+                (c.blue - h.blue).abs() < 1.0 / 255.0, "{} != {}", c.to_hex(), hex);
+    }
+    #[test]
+    fn rgb() {
+        let red = Color::from_rgb255(255, 0, 0);
+        assert_eq!(Color::from_css("rgb(255,0,0)"), Ok(red));
+        assert_eq!(Color::from_css("  RGB( 255 , 0 , 0 )  "), Ok(red));
+        assert_eq!(Color::from_css("rgba(255 0 0)"), Ok(red));
+        assert_eq!(Color::from_css("rgb(300, -1, 0)"), Ok(red));
+        assert_eq!(Color::from_css("rgb(127.5, 0, 0)"), Ok(Color::from_rgb1(0.5, 0.0, 0.0)));
+    }
+    #[test]
+    fn percent_and_alpha() {
+        let half = Color::new(1.0, 0.5, 0.0, 0.5);
+        assert_eq!(Color::from_css("rgb(100% 50% 0% / 50%)"), Ok(half));
+        assert_eq!(Color::from_css("rgb(100% 50% 0% / 0.5)"), Ok(half));
+        assert_eq!(Color::from_css("rgb(255 127.5 0/.5)"), Ok(half));
+        assert_eq!(Color::from_css("rgba(100%, 50%, 0%, 50%)"), Ok(half));
+        assert_eq!(Color::from_css("rgb(100% 50% 0% / 150%)"), Ok(Color::from_rgb1(1.0, 0.5, 0.0)));
+        assert_eq!(Color::from_css("hsl(0 100% 50% / 25%)"), Ok(Color::new(1.0, 0.0, 0.0, 0.25)));
+        assert_eq!(Color::from_css("hwb(0 0% 0% / 25%)"), Ok(Color::new(1.0, 0.0, 0.0, 0.25)));
+        assert_eq!(Color::from_css("oklab(100% 0 0 / 25%)").unwrap().alpha, 0.25);
+    }
+    #[test]
+    fn hsl() {
+        let red = Color::from_rgb255(255, 0, 0);
+        let blue = Color::from_rgb255(0, 0, 255);
+        assert_eq!(Color::from_css("hsl(0, 100%, 50%)"), Ok(red));
+        assert_eq!(Color::from_css("hsl(360 100% 50%)"), Ok(red));
+        assert_eq!(Color::from_css("hsl(240deg 100% 50%)"), Ok(blue));
+        assert_eq!(Color::from_css("hsl(-120deg 100% 50%)"), Ok(blue));
+        assert_eq!(Color::from_css("hsl(0.6667turn 100% 50%)").unwrap().to_hex(), "#0000ff");
+        assert_eq!(Color::from_css("hsl(266.6667grad 100% 50%)").unwrap().to_hex(), "#0000ff");
+        assert_eq!(Color::from_css("hsl(4.18879rad 100% 50%)").unwrap().to_hex(), "#0000ff");
+        assert_eq!(Color::from_css("hsla(0, 0%, 100%, 0.25)"), Ok(Color::new(1.0, 1.0, 1.0, 0.25)));
+    }
+    #[test]
+    fn hwb() {
+        assert_eq!(Color::from_css("hwb(0 0% 0%)"), Ok(Color::from_rgb1(1.0, 0.0, 0.0)));
+        assert_eq!(Color::from_css("hwb(120 0% 50%)"), Ok(Color::from_rgb1(0.0, 0.5, 0.0)));
+        assert_eq!(Color::from_css("hwb(0 60% 60%)"), Ok(Color::from_rgb1(0.5, 0.5, 0.5)));
+        assert_near(Color::from_css("hwb(194 0% 0%)").unwrap(), "#00c3ff");
+    }
+    #[test]
+    fn lab() {
+        // Reference values from https://www.w3.org/TR/css-color-4/
+        assert_near(Color::from_css("lab(29.2345% 39.3825 20.0664)").unwrap(), "#7d2329");
+        assert_near(Color::from_css("lch(29.2345% 44.2 27)").unwrap(), "#7d2329");
+        assert_near(Color::from_css("oklab(40.101% 0.1147 0.0453)").unwrap(), "#7d2329");
+        assert_near(Color::from_css("oklch(40.101% 0.12332 21.555)").unwrap(), "#7d2329");
+        let c = Color::from_css("lab(100 0 0 / 0.5)").unwrap();
+        assert_near(c, "#ffffff");
+        assert_eq!(c.alpha, 0.5);
+        // Out of gamut values are clipped
+        let c = Color::from_css("lch(50% 200 120)").unwrap();
+        assert_eq!(c.red, 0.0);
+    }
+    #[test]
+    fn color() {
+        let c = Color::from_css("color(srgb 1 0.5 0)").unwrap();
+        assert_eq!(c, Color::from_rgb1(1.0, 0.5, 0.0));
+        let c = Color::from_css("color(srgb 100% 50% 0% / 0.5)").unwrap();
+        assert_eq!(c, Color::new(1.0, 0.5, 0.0, 0.5));
+        assert_near(Color::from_css("color(xyz 0.95046 1 1.08906)").unwrap(), "#ffffff");
+        assert_eq!(Color::from_css("color(foo 1 0 0)"),
+                   Err(ParseColorError::InvalidFunction("color(foo)".to_string())));
+    }
+    #[test]
+    fn output() {
+        assert_eq!(fmt_num(1.0, 3), "1");
+        assert_eq!(fmt_num(0.5, 3), "0.5");
+        assert_eq!(fmt_num(-0.0001, 3), "0");
+        assert_eq!(fmt_num(120.0, 2), "120");
+        for s in ["rgb(250 202 222)", "hsl(120 50% 25%)", "hsl(120 50% 25% / 0.5)"].iter() {
+            let c = Color::from_css(s).unwrap();
+            assert_eq!(Color::from_css(&c.to_css_rgba()).unwrap().to_hex_rgba(), c.to_hex_rgba());
+            assert_eq!(Color::from_css(&c.to_css_hsl()).unwrap().to_hex_rgba(), c.to_hex_rgba());
+        }
+        let c = Color::new(0.2, 0.4, 0.6, 0.8);
+        for space in [ColorSpace::Srgb, ColorSpace::SrgbLinear, ColorSpace::DisplayP3,
+                      ColorSpace::A98Rgb, ColorSpace::ProphotoRgb, ColorSpace::Rec2020,
+                      ColorSpace::XyzD50, ColorSpace::XyzD65].iter() {
+            let c2 = Color::from_css(&c.to_css_color(*space)).unwrap();
+            assert!((c2.red - c.red).abs() < 1e-4, "{}", c.to_css_color(*space));
+            assert!((c2.green - c.green).abs() < 1e-4, "{}", c.to_css_color(*space));
+            assert!((c2.blue - c.blue).abs() < 1e-4, "{}", c.to_css_color(*space));
+            assert_eq!(c2.alpha, c.alpha);
+        }
+    }
+    #[test]
+    fn errors() {
+        assert_eq!(Color::from_css(""), Err(ParseColorError::Empty));
+        assert_eq!(Color::from_css("rgb(255, 0, 0"), Err(ParseColorError::InvalidSyntax));
+        assert_eq!(Color::from_css("rgb(255, , 0)"), Err(ParseColorError::InvalidSyntax));
+        assert_eq!(Color::from_css("rgb(255, 0)"), Err(ParseColorError::InvalidArgumentCount(2)));
+        assert_eq!(Color::from_css("lab(50 0 0 / )"), Err(ParseColorError::InvalidSyntax));
+        assert_eq!(Color::from_css("lab(50, 0, 0 / 1)"), Err(ParseColorError::InvalidSyntax));
+        assert_eq!(Color::from_css("rgb(255, x, 0)"),
+                   Err(ParseColorError::InvalidNumber("x".to_string())));
+        assert_eq!(Color::from_css("rgc(255, 0, 0)"),
+                   Err(ParseColorError::InvalidFunction("rgc".to_string())));
+    }
+}
@@ -0,0 +1,187 @@
+//! One dimensional tone curves
+
+use Color;
+
+/// Per-channel tone curve stored as 1D lookup tables
+///
+/// Each table maps an input value in [0,1] onto an output value.
+/// Table entries are evenly spaced across the input range and values
+/// falling between entries are linearly interpolated.
+///
+/// ```
+/// use tint::{Color, Curve};
+/// let invert = Curve::from_lut(&[1.0, 0.0]);
+/// let c = Color::from_rgb1(0.25, 0.5, 1.0).apply_curve(&invert);
+/// assert_eq!(c.to_rgb1(), (0.75, 0.5, 0.0));
+/// ```
+#[derive(Debug,Clone,PartialEq)]
+pub struct Curve {
+    /// Red lookup table
+    pub red: Vec<f64>,
+    /// Green lookup table
+    pub green: Vec<f64>,
+    /// Blue lookup table
+    pub blue: Vec<f64>,
+}
+
+impl Curve {
+    /// Create a new curve from separate red, green and blue lookup tables
+    ///
+    /// Tables may be of different lengths, but must not be empty
+    ///
+    /// # Panics
+    ///   If any of the tables is empty
+    pub fn new(red: Vec<f64>, green: Vec<f64>, blue: Vec<f64>) -> Curve {
+        assert!(!red.is_empty() && !green.is_empty() && !blue.is_empty(),
+                "Curve lookup tables must not be empty");
+        Curve { red, green, blue }
+    }
+    /// Identity curve, leaves all values unchanged
+    ///
+    /// ```
+    /// # use tint::{Color, Curve};
+    /// let c = Color::from_rgb255(250, 202, 222);
+    /// assert_eq!(c.apply_curve(&Curve::identity()), c);
+    /// ```
+    pub fn identity() -> Curve {
+        Curve::from_lut(&[0.0, 1.0])
+    }
+    /// Create a curve using the same lookup table [0, 1] for all channels
+    ///
+    /// # Panics
+    ///   If lut is empty
+    pub fn from_lut(lut: &[f64]) -> Curve {
+        Curve::new(lut.to_vec(), lut.to_vec(), lut.to_vec())
+    }
+    /// Create a curve using the same 8-bit lookup table [0, 255] for all channels
+    ///
+    ///   Tables are typically 256 entries long, as found in
+    ///   exported curve (.amp) files
+    ///
+    /// ```
+    /// # use tint::{Color, Curve};
+    /// let lut : Vec<u8> = (0..256).map(|i| 255 - i as u8).collect();
+    /// let c = Color::from_rgb255(250, 202, 222).apply_curve(&Curve::from_lut255(&lut));
+    /// assert_eq!(c.to_rgb255(), (5, 53, 33));
+    /// ```
+    ///
+    /// # Panics
+    ///   If lut is empty
+    pub fn from_lut255(lut: &[u8]) -> Curve {
+        let lut : Vec<f64> = lut.iter().map(|&v| v as f64 / 255.0).collect();
+        Curve::from_lut(&lut)
+    }
+    /// Create a curve from (input, output) control points [0, 1]
+    ///
+    ///   Points are joined by straight lines and rasterized into a
+    ///   256 entry lookup table.  Inputs outside the first and last
+    ///   points take the value of the nearest point.
+    ///
+    /// ```
+    /// # use tint::{Color, Curve};
+    /// // Increase contrast
+    /// let curve = Curve::from_points(&[(0.0, 0.0), (0.25, 0.15), (0.75, 0.85), (1.0, 1.0)]);
+    /// let c = Color::from_rgb1(0.25, 0.5, 0.75).apply_curve(&curve);
+    /// assert!(c.red < 0.25);
+    /// assert!((c.green - 0.5).abs() < 1e-10);
+    /// assert!(c.blue > 0.75);
+    /// ```
+    ///
+    /// # Panics
+    ///   If points is empty
+    pub fn from_points(points: &[(f64,f64)]) -> Curve {
+        assert!(!points.is_empty(), "Curve requires at least one control point");
+        let mut pts = points.to_vec();
+        pts.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+        let n = 256;
+        let lut : Vec<f64> = (0..n).map(|i| {
+            let x = i as f64 / (n - 1) as f64;
+            interp_points(&pts, x)
+        }).collect();
+        Curve::from_lut(&lut)
+    }
+    /// Apply curve to a single red, green and blue triple
+    pub fn eval(&self, r: f64, g: f64, b: f64) -> (f64,f64,f64) {
+        (interp_lut(&self.red, r),
+         interp_lut(&self.green, g),
+         interp_lut(&self.blue, b))
+    }
+}
+
+impl Color {
+    /// Apply a tone curve to the red, green and blue components
+    ///
+    ///   Alpha is unchanged
+    pub fn apply_curve(&self, curve: &Curve) -> Color {
+        let (r,g,b) = curve.eval(self.red, self.green, self.blue);
+        Color::new(r, g, b, self.alpha)
+    }
+}
+
+fn interp_lut(lut: &[f64], x: f64) -> f64 {
+    let n = lut.len();
+    if n == 1 {
+        return lut[0];
+    }
+    let pos = x.clamp(0.0, 1.0) * (n - 1) as f64;
+    let i = (pos.floor() as usize).min(n - 2);
+    let f = pos - i as f64;
+    lut[i] + (lut[i+1] - lut[i]) * f
+}
+
+fn interp_points(pts: &[(f64,f64)], x: f64) -> f64 {
+    if x <= pts[0].0 {
+        return pts[0].1;
+    }
+    for w in pts.windows(2) {
+        let (x0, y0) = w[0];
+        let (x1, y1) = w[1];
+        if x <= x1 {
+            if x1 - x0 <= 0.0 {
+                return y1;
+            }
+            return y0 + (y1 - y0) * (x - x0) / (x1 - x0);
+        }
+    }
+    pts[pts.len()-1].1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn lut() {
+        assert_eq!(interp_lut(&[0.5], 0.2), 0.5);
+        assert_eq!(interp_lut(&[0.0, 1.0], 0.25), 0.25);
+        assert_eq!(interp_lut(&[0.0, 0.5, 0.0], 0.75), 0.25);
+        assert_eq!(interp_lut(&[0.0, 1.0], -1.0), 0.0);
+        assert_eq!(interp_lut(&[0.0, 1.0], 2.0), 1.0);
+    }
+    #[test]
+    fn points() {
+        let pts = [(0.2, 0.0), (0.8, 1.0)];
+        assert_eq!(interp_points(&pts, 0.0), 0.0);
+        assert!((interp_points(&pts, 0.5) - 0.5).abs() < 1e-12);
+        assert_eq!(interp_points(&pts, 1.0), 1.0);
+    }
+    #[test]
+    #[should_panic]
+    fn empty_table() {
+        Curve::new(vec![0.0], vec![], vec![1.0]);
+    }
+    #[test]
+    #[should_panic]
+    fn empty_lut() {
+        Curve::from_lut(&[]);
+    }
+    #[test]
+    #[should_panic]
+    fn empty_lut255() {
+        Curve::from_lut255(&[]);
+    }
+    #[test]
+    #[should_panic]
+    fn empty_points() {
+        Curve::from_points(&[]);
+    }
+}
@@ -0,0 +1,212 @@
+//! Color vision deficiency simulation
+//!
+//! Machado, Oliveira and Fernandes (2009), "A Physiologically-based
+//! Model for Simulation of Color Vision Deficiency"
+//!
+//! Fidaner, Lin and Ozguven (2005), "Analysis of Color Blindness",
+//! for daltonization
+
+use space::linear_to_srgb;
+use Color;
+
+/// Kind of color vision deficiency
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Hash)]
+pub enum Cvd {
+    /// Missing or anomalous long wavelength (red) cones
+    Protanopia,
+    /// Missing or anomalous medium wavelength (green) cones
+    Deuteranopia,
+    /// Missing or anomalous short wavelength (blue) cones
+    Tritanopia,
+}
+
+impl Cvd {
+    /// All kinds of color vision deficiency
+    pub fn all() -> Vec<Cvd> {
+        vec![Cvd::Protanopia, Cvd::Deuteranopia, Cvd::Tritanopia]
+    }
+    /// Linear RGB matrix for full severity
+    fn matrix(&self) -> [[f64;3];3] {
+        match *self {
+            Cvd::Protanopia => [[ 0.152286,  1.052583, -0.204868],
+                                [ 0.114503,  0.786281,  0.099216],
+                                [-0.003882, -0.048116,  1.051998]],
+            Cvd::Deuteranopia => [[ 0.367322,  0.860646, -0.227968],
+                                  [ 0.280085,  0.672501,  0.047413],
+                                  [-0.011820,  0.042940,  0.968881]],
+            Cvd::Tritanopia => [[ 1.255528, -0.076749, -0.178779],
+                                [-0.078411,  0.930809,  0.147602],
+                                [ 0.004733,  0.691367,  0.303900]],
+        }
+    }
+}
+
+/// Distinguishability of a palette under one color vision deficiency
+#[derive(Debug,Clone,PartialEq)]
+pub struct CvdReport {
+    /// Simulated deficiency
+    pub kind: Cvd,
+    /// Smallest CIEDE2000 difference between any two simulated colors
+    pub min_delta_e: f64,
+    /// Indices of the pair with the smallest difference, if any
+    pub closest: Option<(usize, usize)>,
+    /// Indices of pairs with a difference below the threshold
+    pub confusable: Vec<(usize, usize)>,
+}
+
+/// Check a categorical palette for colors that become indistinguishable
+///
+///   Each color is simulated at full severity for every deficiency and
+///   all pairs are compared with CIEDE2000.  Pairs closer than
+///   threshold are reported as confusable
+///
+/// ```
+/// # use tint::{cvd_check, Color};
+/// let colors = [Color::from("red"), Color::from("green"), Color::from("blue")];
+/// for r in cvd_check(&colors, 10.0) {
+///     println!("{:?} {:.1} {:?}", r.kind, r.min_delta_e, r.confusable);
+/// }
+/// ```
+pub fn cvd_check(colors: &[Color], threshold: f64) -> Vec<CvdReport> {
+    Cvd::all().into_iter().map(|kind| {
+        let sim: Vec<Color> = colors.iter().map(|c| c.simulate_cvd(kind, 1.0)).collect();
+        let mut report = CvdReport {
+            kind,
+            min_delta_e: f64::INFINITY,
+            closest: None,
+            confusable: vec![],
+        };
+        for i in 0..sim.len() {
+            for j in i+1..sim.len() {
+                let de = sim[i].delta_e2000(&sim[j]);
+                if de < report.min_delta_e {
+                    report.min_delta_e = de;
+                    report.closest = Some((i, j));
+                }
+                if de < threshold {
+                    report.confusable.push((i, j));
+                }
+            }
+        }
+        report
+    }).collect()
+}
+
+/// Daltonize a palette, see Color::daltonize()
+///
+/// ```
+/// # use tint::{daltonize_palette, Color, Cvd};
+/// let colors = [Color::from("red"), Color::from("green")];
+/// let fixed = daltonize_palette(&colors, Cvd::Deuteranopia, 1.0);
+/// assert_eq!(fixed.len(), 2);
+/// ```
+pub fn daltonize_palette(colors: &[Color], kind: Cvd, severity: f64) -> Vec<Color> {
+    colors.iter().map(|c| c.daltonize(kind, severity)).collect()
+}
+
+impl Color {
+    /// Simulate how the Color appears with a color vision deficiency
+    ///
+    ///   Severity [0,1] runs from normal vision to dichromacy,
+    ///   interpolating linearly towards the Machado et al. dichromat
+    ///   matrix.  The simulation is applied in linear RGB and the result
+    ///   is clipped to [0,1].  Alpha is unchanged
+    ///
+    /// ```
+    /// # use tint::{Color, Cvd};
+    /// let red = Color::from_rgb255(255, 0, 0);
+    /// let green = Color::from_rgb255(0, 128, 0);
+    /// // Red and green are much harder to tell apart
+    /// let before = red.delta_e2000(&green);
+    /// let after = red.simulate_cvd(Cvd::Deuteranopia, 1.0)
+    ///     .delta_e2000(&green.simulate_cvd(Cvd::Deuteranopia, 1.0));
+    /// assert!(after < before / 2.0);
+    /// assert_eq!(red.simulate_cvd(Cvd::Protanopia, 0.0), red);
+    /// ```
+    pub fn simulate_cvd(&self, kind: Cvd, severity: f64) -> Color {
+        let s = severity.clamp(0.0, 1.0);
+        if s == 0.0 {
+            return *self;
+        }
+        let m = kind.matrix();
+        let (r,g,b) = self.to_linear_rgb();
+        let v = [r, g, b];
+        let out: Vec<f64> = (0..3).map(|i| {
+            let x: f64 = (0..3).map(|j| m[i][j] * v[j]).sum();
+            let x = v[i] + (x - v[i]) * s;
+            linear_to_srgb(x.clamp(0.0, 1.0))
+        }).collect();
+        Color::new(out[0], out[1], out[2], self.alpha)
+    }
+    /// Shift the Color to be more distinguishable with a color vision deficiency
+    ///
+    ///   The information lost in simulate_cvd() is redistributed to
+    ///   channels that remain visible, so colors that would be confused
+    ///   become easier to tell apart.  The result is clipped to [0,1]
+    ///   and alpha is unchanged
+    ///
+    /// ```
+    /// # use tint::{Color, Cvd};
+    /// let red = Color::from_rgb255(255, 0, 0);
+    /// let green = Color::from_rgb255(0, 128, 0);
+    /// let sim = |c: Color| c.simulate_cvd(Cvd::Deuteranopia, 1.0);
+    /// let before = sim(red).delta_e2000(&sim(green));
+    /// let fixed = red.daltonize(Cvd::Deuteranopia, 1.0);
+    /// assert!(sim(fixed).delta_e2000(&sim(green.daltonize(Cvd::Deuteranopia, 1.0))) > before);
+    /// ```
+    pub fn daltonize(&self, kind: Cvd, severity: f64) -> Color {
+        let sim = self.simulate_cvd(kind, severity);
+        let (er, eg, eb) = (self.red - sim.red, self.green - sim.green, self.blue - sim.blue);
+        let (dr, dg, db) = match kind {
+            Cvd::Protanopia | Cvd::Deuteranopia => (0.0, 0.7 * er + eg, 0.7 * er + eb),
+            Cvd::Tritanopia => (er + 0.7 * eb, eg + 0.7 * eb, 0.0),
+        };
+        Color::new((self.red + dr).clamp(0.0, 1.0),
+                   (self.green + dg).clamp(0.0, 1.0),
+                   (self.blue + db).clamp(0.0, 1.0),
+                   self.alpha)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn simulate() {
+        let c = Color::new(0.2, 0.4, 0.6, 0.5);
+        let white = Color::new(1.0, 1.0, 1.0, 1.0);
+        for kind in Cvd::all() {
+            assert!(c.simulate_cvd(kind, 0.0).approx_eq(&c, 1e-12));
+            assert_eq!(c.simulate_cvd(kind, 1.0).alpha, 0.5);
+            // Rows sum to one, so neutral colors are unchanged
+            assert!(white.simulate_cvd(kind, 1.0).approx_eq(&white, 1e-5));
+        }
+        let half = c.simulate_cvd(Cvd::Tritanopia, 0.5);
+        let full = c.simulate_cvd(Cvd::Tritanopia, 1.0);
+        assert!(c.delta_e2000(&half) < c.delta_e2000(&full));
+    }
+    #[test]
+    fn daltonize() {
+        let gray = Color::new(0.5, 0.5, 0.5, 0.5);
+        for kind in Cvd::all() {
+            assert!(gray.daltonize(kind, 1.0).approx_eq(&gray, 1e-5));
+            assert_eq!(gray.daltonize(kind, 0.0), gray);
+        }
+        let c = [Color::new(0.2, 0.4, 0.6, 1.0), Color::new(0.9, 0.1, 0.1, 1.0)];
+        let d = daltonize_palette(&c, Cvd::Protanopia, 1.0);
+        assert_eq!(d[1], c[1].daltonize(Cvd::Protanopia, 1.0));
+    }
+    #[test]
+    fn check() {
+        let r = cvd_check(&[Color::new(0.5, 0.5, 0.5, 1.0)], 10.0);
+        assert_eq!(r.len(), 3);
+        assert!(r.iter().all(|x| x.closest.is_none() && x.min_delta_e.is_infinite()));
+        let colors = [Color::from("red"), Color::from("green"), Color::from("white")];
+        let r = cvd_check(&colors, 20.0);
+        let deutan = r.iter().find(|x| x.kind == Cvd::Deuteranopia).unwrap();
+        assert_eq!(deutan.closest, Some((0, 1)));
+        assert_eq!(deutan.confusable, vec![(0, 1)]);
+        let tritan = r.iter().find(|x| x.kind == Cvd::Tritanopia).unwrap();
+        assert!(tritan.confusable.is_empty());
+    }
+}
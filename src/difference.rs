@@ -0,0 +1,237 @@
+//! Color difference formulas, Delta E
+
+use Color;
+
+/// Weights for the CIE94 color difference
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Hash,Default)]
+pub enum Cie94 {
+    /// kL = 1, K1 = 0.045, K2 = 0.015, the default
+    #[default]
+    GraphicArts,
+    /// kL = 2, K1 = 0.048, K2 = 0.014
+    Textiles,
+}
+
+impl Cie94 {
+    fn weights(&self) -> (f64, f64, f64) {
+        match *self {
+            Cie94::GraphicArts => (1.0, 0.045, 0.015),
+            Cie94::Textiles => (2.0, 0.048, 0.014),
+        }
+    }
+}
+
+/// Differences in lightness, chroma and (squared) hue, and the reference chroma
+fn lch_diffs(a: (f64,f64,f64), b: (f64,f64,f64)) -> (f64, f64, f64, f64) {
+    let c1 = a.1.hypot(a.2);
+    let c2 = b.1.hypot(b.2);
+    let dc = c1 - c2;
+    let dh2 = ((a.1 - b.1).powi(2) + (a.2 - b.2).powi(2) - dc * dc).max(0.0);
+    (a.0 - b.0, dc, dh2, c1)
+}
+
+impl Color {
+    /// CIE94 color difference, with the Color as the reference
+    ///
+    ///   The formula is not symmetric; swapping the colors gives a
+    ///   slightly different value
+    ///
+    /// ```
+    /// # use tint::{Cie94, Color};
+    /// let a = Color::from_lab(50.0, 2.6772, -79.7751);
+    /// let b = Color::from_lab(50.0, 0.0, -82.7485);
+    /// assert!((a.delta_e94(&b, Cie94::GraphicArts) - 1.3950).abs() < 1e-4);
+    /// ```
+    pub fn delta_e94(&self, other: &Color, weights: Cie94) -> f64 {
+        let (kl, k1, k2) = weights.weights();
+        let (dl, dc, dh2, c1) = lch_diffs(self.to_lab(), other.to_lab());
+        let sc = 1.0 + k1 * c1;
+        let sh = 1.0 + k2 * c1;
+        ((dl / kl).powi(2) + (dc / sc).powi(2) + dh2 / (sh * sh)).sqrt()
+    }
+    /// CMC l:c color difference, with the Color as the reference
+    ///
+    ///   Typical weights are 2:1 for acceptability and 1:1 for
+    ///   perceptibility.  The formula is not symmetric
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let a = Color::from_lab(50.0, 2.6772, -79.7751);
+    /// let b = Color::from_lab(50.0, 0.0, -82.7485);
+    /// assert!((a.delta_e_cmc(&b, 2.0, 1.0) - 1.7387).abs() < 1e-4);
+    /// ```
+    pub fn delta_e_cmc(&self, other: &Color, l: f64, c: f64) -> f64 {
+        let lab1 = self.to_lab();
+        let (dl, dc, dh2, c1) = lch_diffs(lab1, other.to_lab());
+        let l1 = lab1.0;
+        let h1 = lab1.2.atan2(lab1.1).to_degrees().rem_euclid(360.0);
+        let sl = if l1 < 16.0 { 0.511 } else { 0.040975 * l1 / (1.0 + 0.01765 * l1) };
+        let sc = 0.0638 * c1 / (1.0 + 0.0131 * c1) + 0.638;
+        let f = (c1.powi(4) / (c1.powi(4) + 1900.0)).sqrt();
+        let t = if (164.0..=345.0).contains(&h1) {
+            0.56 + (0.2 * (h1 + 168.0).to_radians().cos()).abs()
+        } else {
+            0.36 + (0.4 * (h1 + 35.0).to_radians().cos()).abs()
+        };
+        let sh = sc * (f * t + 1.0 - f);
+        ((dl / (l * sl)).powi(2) + (dc / (c * sc)).powi(2) + dh2 / (sh * sh)).sqrt()
+    }
+    /// Redmean weighted RGB distance, [0, ~765]
+    ///
+    ///   A cheap approximation to perceptual difference that avoids
+    ///   converting to Lab, weighting red and blue by the mean red
+    ///   level.  Components are scaled to [0,255]; alpha is ignored
+    ///
+    ///   https://www.compuphase.com/cmetric.htm
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let black = Color::from_rgb255(0, 0, 0);
+    /// let red = Color::from_rgb255(255, 0, 0);
+    /// assert!((black.distance_redmean(&red) - 255.0 * (2.0 + 127.5 / 256.0f64).sqrt()).abs() < 1e-9);
+    /// ```
+    pub fn distance_redmean(&self, other: &Color) -> f64 {
+        let rbar = (self.red + other.red) * 255.0 / 2.0;
+        let dr = (self.red - other.red) * 255.0;
+        let dg = (self.green - other.green) * 255.0;
+        let db = (self.blue - other.blue) * 255.0;
+        ((2.0 + rbar / 256.0) * dr * dr + 4.0 * dg * dg
+         + (2.0 + (255.0 - rbar) / 256.0) * db * db).sqrt()
+    }
+    /// CIEDE2000 color difference
+    ///
+    ///   The current CIE recommendation, including the blue region
+    ///   rotation term.  A difference of about 1 is just noticeable
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let a = Color::from_lab(50.0, 2.6772, -79.7751);
+    /// let b = Color::from_lab(50.0, 0.0, -82.7485);
+    /// assert!((a.delta_e2000(&b) - 2.0425).abs() < 1e-4);
+    /// ```
+    pub fn delta_e2000(&self, other: &Color) -> f64 {
+        ciede2000(self.to_lab(), other.to_lab())
+    }
+}
+
+/// CIEDE2000 of two Lab colors, after Sharma, Wu and Dalal (2005)
+fn ciede2000(lab1: (f64,f64,f64), lab2: (f64,f64,f64)) -> f64 {
+    let (l1, a1, b1) = lab1;
+    let (l2, a2, b2) = lab2;
+    let pow25_7 = 25f64.powi(7);
+    let cbar = (a1.hypot(b1) + a2.hypot(b2)) / 2.0;
+    let g = 0.5 * (1.0 - (cbar.powi(7) / (cbar.powi(7) + pow25_7)).sqrt());
+    let (a1, a2) = ((1.0 + g) * a1, (1.0 + g) * a2);
+    let (c1, c2) = (a1.hypot(b1), a2.hypot(b2));
+    let hue = |a: f64, b: f64| {
+        if a == 0.0 && b == 0.0 { 0.0 } else { b.atan2(a).to_degrees().rem_euclid(360.0) }
+    };
+    let (h1, h2) = (hue(a1, b1), hue(a2, b2));
+    let chromatic = c1 * c2 != 0.0;
+
+    let dl = l2 - l1;
+    let dc = c2 - c1;
+    let dh = if !chromatic {
+        0.0
+    } else if (h2 - h1).abs() <= 180.0 {
+        h2 - h1
+    } else if h2 - h1 > 180.0 {
+        h2 - h1 - 360.0
+    } else {
+        h2 - h1 + 360.0
+    };
+    let dh = 2.0 * (c1 * c2).sqrt() * (dh / 2.0).to_radians().sin();
+
+    let lbar = (l1 + l2) / 2.0;
+    let cbar = (c1 + c2) / 2.0;
+    let hbar = if !chromatic {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180.0 {
+        (h1 + h2) / 2.0
+    } else if h1 + h2 < 360.0 {
+        (h1 + h2 + 360.0) / 2.0
+    } else {
+        (h1 + h2 - 360.0) / 2.0
+    };
+    let cos = |deg: f64| deg.to_radians().cos();
+    let t = 1.0 - 0.17 * cos(hbar - 30.0) + 0.24 * cos(2.0 * hbar)
+        + 0.32 * cos(3.0 * hbar + 6.0) - 0.20 * cos(4.0 * hbar - 63.0);
+    let dtheta = 30.0 * (-((hbar - 275.0) / 25.0).powi(2)).exp();
+    let rc = 2.0 * (cbar.powi(7) / (cbar.powi(7) + pow25_7)).sqrt();
+    let sl = 1.0 + 0.015 * (lbar - 50.0).powi(2) / (20.0 + (lbar - 50.0).powi(2)).sqrt();
+    let sc = 1.0 + 0.045 * cbar;
+    let sh = 1.0 + 0.015 * cbar * t;
+    let rt = -(2.0 * dtheta).to_radians().sin() * rc;
+
+    let (l, c, h) = (dl / sl, dc / sc, dh / sh);
+    (l * l + c * c + h * h + rt * c * h).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn cie94_cmc() {
+        let a = Color::new(0.2, 0.4, 0.6, 1.0);
+        let b = Color::new(0.25, 0.4, 0.55, 1.0);
+        assert!(a.delta_e94(&a, Cie94::Textiles) < 1e-9);
+        assert!(a.delta_e_cmc(&a, 1.0, 1.0) < 1e-9);
+        assert!(a.delta_e94(&b, Cie94::Textiles) < a.delta_e94(&b, Cie94::GraphicArts));
+        assert!(a.delta_e_cmc(&b, 2.0, 1.0) < a.delta_e_cmc(&b, 1.0, 1.0));
+        assert!((a.delta_e94(&b, Cie94::default()) - b.delta_e94(&a, Cie94::default())).abs() > 1e-6);
+    }
+    #[test]
+    fn redmean() {
+        let a = Color::new(0.2, 0.4, 0.6, 1.0);
+        let b = Color::new(0.25, 0.4, 0.55, 0.0);
+        assert_eq!(a.distance_redmean(&a), 0.0);
+        assert_eq!(a.distance_redmean(&b), b.distance_redmean(&a));
+        let black = Color::new(0.0, 0.0, 0.0, 1.0);
+        let white = Color::new(1.0, 1.0, 1.0, 1.0);
+        assert!(black.distance_redmean(&white) < 765.0);
+    }
+    #[test]
+    fn ciede2000_sharma() {
+        // Sharma, Wu and Dalal (2005), Table 1
+        let data = [
+            ((50.0000, 2.6772, -79.7751), (50.0000, 0.0000, -82.7485), 2.0425),
+            ((50.0000, 3.1571, -77.2803), (50.0000, 0.0000, -82.7485), 2.8615),
+            ((50.0000, 2.8361, -74.0200), (50.0000, 0.0000, -82.7485), 3.4412),
+            ((50.0000, -1.3802, -84.2814), (50.0000, 0.0000, -82.7485), 1.0000),
+            ((50.0000, -1.1848, -84.8006), (50.0000, 0.0000, -82.7485), 1.0000),
+            ((50.0000, -0.9009, -85.5211), (50.0000, 0.0000, -82.7485), 1.0000),
+            ((50.0000, 0.0000, 0.0000), (50.0000, -1.0000, 2.0000), 2.3669),
+            ((50.0000, -1.0000, 2.0000), (50.0000, 0.0000, 0.0000), 2.3669),
+            ((50.0000, 2.4900, -0.0010), (50.0000, -2.4900, 0.0009), 7.1792),
+            ((50.0000, 2.4900, -0.0010), (50.0000, -2.4900, 0.0010), 7.1792),
+            ((50.0000, 2.4900, -0.0010), (50.0000, -2.4900, 0.0011), 7.2195),
+            ((50.0000, 2.4900, -0.0010), (50.0000, -2.4900, 0.0012), 7.2195),
+            ((50.0000, -0.0010, 2.4900), (50.0000, 0.0009, -2.4900), 4.8045),
+            ((50.0000, -0.0010, 2.4900), (50.0000, 0.0010, -2.4900), 4.8045),
+            ((50.0000, -0.0010, 2.4900), (50.0000, 0.0011, -2.4900), 4.7461),
+            ((50.0000, 2.5000, 0.0000), (50.0000, 0.0000, -2.5000), 4.3065),
+            ((50.0000, 2.5000, 0.0000), (73.0000, 25.0000, -18.0000), 27.1492),
+            ((50.0000, 2.5000, 0.0000), (61.0000, -5.0000, 29.0000), 22.8977),
+            ((50.0000, 2.5000, 0.0000), (56.0000, -27.0000, -3.0000), 31.9030),
+            ((50.0000, 2.5000, 0.0000), (58.0000, 24.0000, 15.0000), 19.4535),
+            ((50.0000, 2.5000, 0.0000), (50.0000, 3.1736, 0.5854), 1.0000),
+            ((50.0000, 2.5000, 0.0000), (50.0000, 3.2972, 0.0000), 1.0000),
+            ((50.0000, 2.5000, 0.0000), (50.0000, 1.8634, 0.5757), 1.0000),
+            ((50.0000, 2.5000, 0.0000), (50.0000, 3.2592, 0.3350), 1.0000),
+            ((60.2574, -34.0099, 36.2677), (60.4626, -34.1751, 39.4387), 1.2644),
+            ((63.0109, -31.0961, -5.8663), (62.8187, -29.7946, -4.0864), 1.2630),
+            ((61.2901, 3.7196, -5.3901), (61.4292, 2.2480, -4.9620), 1.8731),
+            ((35.0831, -44.1164, 3.7933), (35.0232, -40.0716, 1.5901), 1.8645),
+            ((22.7233, 20.0904, -46.6940), (23.0331, 14.9730, -42.5619), 2.0373),
+            ((36.4612, 47.8580, 18.3852), (36.2715, 50.5065, 21.2231), 1.4146),
+            ((90.8027, -2.0831, 1.4410), (91.1528, -1.6435, 0.0447), 1.4441),
+            ((90.9257, -0.5406, -0.9208), (88.6381, -0.8985, -0.7239), 1.5381),
+            ((6.7747, -0.2908, -2.4247), (5.8714, -0.0985, -2.2286), 0.6377),
+            ((2.0776, 0.0795, -1.1350), (0.9033, -0.0636, -0.5514), 0.9082),
+        ];
+        for (i, &(a, b, de)) in data.iter().enumerate() {
+            assert!((ciede2000(a, b) - de).abs() < 1e-4, "pair {}", i + 1);
+            assert!((ciede2000(b, a) - de).abs() < 1e-4, "pair {} swapped", i + 1);
+        }
+    }
+}
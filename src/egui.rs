@@ -0,0 +1,37 @@
+//! Conversions to and from egui colors, with the "egui" feature
+//!
+//! egui stores Color32 with alpha premultiplied in linear light, so
+//!   conversions go through its unmultiplied constructors
+
+use ecolor::Color32;
+use Color;
+
+impl From<Color> for Color32 {
+    fn from(c: Color) -> Color32 {
+        let [r, g, b, a] = c.to_rgba8();
+        Color32::from_rgba_unmultiplied(r, g, b, a)
+    }
+}
+
+impl From<Color32> for Color {
+    fn from(c: Color32) -> Color {
+        Color::from(c.to_srgba_unmultiplied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn egui() {
+        let c = Color::from("#ff8000");
+        assert_eq!(Color32::from(c), Color32::from_rgb(255, 128, 0));
+        assert_eq!(Color::from(Color32::from(c)), c);
+        let half = Color::new(1.0, 1.0, 1.0, 0.5);
+        let c32 = Color32::from(half);
+        assert!(c32.r() < 255);
+        let back = Color::from(c32);
+        assert!((back.red - 1.0).abs() < 0.02);
+        assert_eq!(Color::from(Color32::TRANSPARENT).alpha, 0.0);
+    }
+}
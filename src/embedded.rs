@@ -0,0 +1,59 @@
+//! Conversions to and from embedded-graphics pixel colors, with the
+//! "embedded-graphics" feature
+
+use embedded_graphics_core::pixelcolor::raw::RawU16;
+use embedded_graphics_core::pixelcolor::{IntoStorage, Rgb565, Rgb888, RgbColor};
+use Color;
+
+impl From<Color> for Rgb888 {
+    fn from(c: Color) -> Rgb888 {
+        let (r, g, b) = c.to_rgb255();
+        Rgb888::new(r, g, b)
+    }
+}
+
+impl From<Rgb888> for Color {
+    fn from(c: Rgb888) -> Color {
+        Color::from_rgb255(c.r(), c.g(), c.b())
+    }
+}
+
+impl From<Color> for Rgb565 {
+    /// Rgb565 color, as in Color::to_rgb565()
+    ///
+    /// ```
+    /// # extern crate embedded_graphics_core;
+    /// # extern crate tint;
+    /// # use tint::Color;
+    /// # use embedded_graphics_core::pixelcolor::Rgb565;
+    /// # fn main() {
+    /// assert_eq!(Rgb565::from(Color::from("orange")), Rgb565::new(31, 41, 0));
+    /// # }
+    /// ```
+    fn from(c: Color) -> Rgb565 {
+        Rgb565::from(RawU16::new(c.to_rgb565()))
+    }
+}
+
+impl From<Rgb565> for Color {
+    /// Color from an Rgb565 color, as in Color::from_rgb565()
+    fn from(c: Rgb565) -> Color {
+        Color::from_rgb565(c.into_storage())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn embedded() {
+        let c = Color::from_rgb255(10, 20, 30);
+        assert_eq!(Color::from(Rgb888::from(c)), c);
+        assert_eq!(Rgb565::from(Color::from("white")), Rgb565::WHITE);
+        assert_eq!(Color::from(Rgb565::RED), Color::from("red"));
+        let c = Rgb565::new(7, 40, 19);
+        assert_eq!(Rgb565::from(Color::from(c)), c);
+        assert_eq!(Color::from(c), Color::from_rgb565(c.into_storage()));
+        assert_eq!(Color::from(c).to_rgb255().1, 162);
+    }
+}
@@ -0,0 +1,51 @@
+//! Error types
+
+use std::error::Error;
+use std::fmt;
+
+/// Error describing why a color could not be parsed
+#[derive(Debug,Clone,PartialEq)]
+pub enum ParseColorError {
+    /// Input string was empty
+    Empty,
+    /// Name is not a known color name
+    UnknownName(String),
+    /// Hex string has an unsupported number of digits
+    InvalidHexLength(usize),
+    /// Hex string contains a character that is not a hexadecimal digit
+    InvalidHexDigit(char),
+    /// Functional notation is malformed, e.g. unbalanced parentheses
+    InvalidSyntax,
+    /// Function name is not a supported color function
+    InvalidFunction(String),
+    /// Function was given the wrong number of arguments, or a slice
+    /// the wrong number of components
+    InvalidArgumentCount(usize),
+    /// Function argument is not a valid number
+    InvalidNumber(String),
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseColorError::Empty =>
+                write!(f, "empty color string"),
+            ParseColorError::UnknownName(ref name) =>
+                write!(f, "unknown color name: {:?}", name),
+            ParseColorError::InvalidHexLength(n) =>
+                write!(f, "invalid number of hex digits: {}", n),
+            ParseColorError::InvalidHexDigit(c) =>
+                write!(f, "invalid hex digit: {:?}", c),
+            ParseColorError::InvalidSyntax =>
+                write!(f, "invalid color function syntax"),
+            ParseColorError::InvalidFunction(ref name) =>
+                write!(f, "unknown color function: {:?}", name),
+            ParseColorError::InvalidArgumentCount(n) =>
+                write!(f, "invalid number of arguments: {}", n),
+            ParseColorError::InvalidNumber(ref v) =>
+                write!(f, "invalid number: {:?}", v),
+        }
+    }
+}
+
+impl Error for ParseColorError {}
@@ -0,0 +1,130 @@
+//! Color matrix filters, as in SVG feColorMatrix and CSS filter functions
+
+use Color;
+
+/// 4x5 color matrix, rows produce red, green, blue and alpha
+///
+///   Each row is applied to (r, g, b, a, 1), so the last column is an
+///   offset.  Components are gamma encoded sRGB, as with CSS filter
+///   functions, and results are clipped to [0,1]
+///
+/// ```
+/// # use tint::{Color, ColorMatrix};
+/// let swap = ColorMatrix::new([[0.0, 0.0, 1.0, 0.0, 0.0],
+///                              [0.0, 1.0, 0.0, 0.0, 0.0],
+///                              [1.0, 0.0, 0.0, 0.0, 0.0],
+///                              [0.0, 0.0, 0.0, 1.0, 0.0]]);
+/// let c = Color::from_rgb255(255, 128, 0);
+/// assert_eq!(c.filter(&swap).to_rgb255(), (0, 128, 255));
+/// ```
+#[derive(Debug,Copy,Clone,PartialEq)]
+pub struct ColorMatrix {
+    rows: [[f64;5];4],
+}
+
+impl ColorMatrix {
+    /// Create a new matrix from rows
+    pub fn new(rows: [[f64;5];4]) -> ColorMatrix {
+        ColorMatrix { rows }
+    }
+    /// Matrix that leaves colors unchanged
+    pub fn identity() -> ColorMatrix {
+        ColorMatrix::new([[1.0, 0.0, 0.0, 0.0, 0.0],
+                          [0.0, 1.0, 0.0, 0.0, 0.0],
+                          [0.0, 0.0, 1.0, 0.0, 0.0],
+                          [0.0, 0.0, 0.0, 1.0, 0.0]])
+    }
+    /// CSS sepia(amount), amount [0,1] is clipped
+    pub fn sepia(amount: f64) -> ColorMatrix {
+        let t = 1.0 - amount.clamp(0.0, 1.0);
+        ColorMatrix::new([[0.393 + 0.607 * t, 0.769 - 0.769 * t, 0.189 - 0.189 * t, 0.0, 0.0],
+                          [0.349 - 0.349 * t, 0.686 + 0.314 * t, 0.168 - 0.168 * t, 0.0, 0.0],
+                          [0.272 - 0.272 * t, 0.534 - 0.534 * t, 0.131 + 0.869 * t, 0.0, 0.0],
+                          [0.0, 0.0, 0.0, 1.0, 0.0]])
+    }
+    /// SVG feColorMatrix type="saturate", 0 is gray, 1 is unchanged
+    pub fn saturate(s: f64) -> ColorMatrix {
+        ColorMatrix::new([[0.213 + 0.787 * s, 0.715 - 0.715 * s, 0.072 - 0.072 * s, 0.0, 0.0],
+                          [0.213 - 0.213 * s, 0.715 + 0.285 * s, 0.072 - 0.072 * s, 0.0, 0.0],
+                          [0.213 - 0.213 * s, 0.715 - 0.715 * s, 0.072 + 0.928 * s, 0.0, 0.0],
+                          [0.0, 0.0, 0.0, 1.0, 0.0]])
+    }
+    /// SVG feColorMatrix type="hueRotate", angle in degrees
+    pub fn hue_rotate(degrees: f64) -> ColorMatrix {
+        let (s, c) = degrees.to_radians().sin_cos();
+        ColorMatrix::new([[0.213 + c * 0.787 - s * 0.213,
+                           0.715 - c * 0.715 - s * 0.715,
+                           0.072 - c * 0.072 + s * 0.928, 0.0, 0.0],
+                          [0.213 - c * 0.213 + s * 0.143,
+                           0.715 + c * 0.285 + s * 0.140,
+                           0.072 - c * 0.072 - s * 0.283, 0.0, 0.0],
+                          [0.213 - c * 0.213 - s * 0.787,
+                           0.715 - c * 0.715 + s * 0.715,
+                           0.072 + c * 0.928 + s * 0.072, 0.0, 0.0],
+                          [0.0, 0.0, 0.0, 1.0, 0.0]])
+    }
+    /// SVG feColorMatrix type="luminanceToAlpha"
+    pub fn luminance_to_alpha() -> ColorMatrix {
+        ColorMatrix::new([[0.0, 0.0, 0.0, 0.0, 0.0],
+                          [0.0, 0.0, 0.0, 0.0, 0.0],
+                          [0.0, 0.0, 0.0, 0.0, 0.0],
+                          [0.2125, 0.7154, 0.0721, 0.0, 0.0]])
+    }
+    /// Rows of the matrix
+    pub fn rows(&self) -> [[f64;5];4] {
+        self.rows
+    }
+    /// Apply the matrix to a Color
+    pub fn apply(&self, color: &Color) -> Color {
+        let v = [color.red, color.green, color.blue, color.alpha, 1.0];
+        let out: Vec<f64> = self.rows.iter()
+            .map(|row| row.iter().zip(v.iter()).map(|(m, x)| m * x).sum::<f64>())
+            .map(|x| x.clamp(0.0, 1.0))
+            .collect();
+        Color::new(out[0], out[1], out[2], out[3])
+    }
+}
+
+impl Default for ColorMatrix {
+    fn default() -> ColorMatrix {
+        ColorMatrix::identity()
+    }
+}
+
+impl Color {
+    /// Apply a ColorMatrix filter
+    pub fn filter(&self, matrix: &ColorMatrix) -> Color {
+        matrix.apply(self)
+    }
+    /// Full sepia tone, as CSS sepia(1)
+    ///
+    ///   Alpha is unchanged
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let white = Color::from_rgb255(255, 255, 255);
+    /// assert_eq!(white.sepia().to_rgb255(), (255, 255, 238));
+    /// ```
+    pub fn sepia(&self) -> Color {
+        self.filter(&ColorMatrix::sepia(1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn matrices() {
+        let c = Color::new(0.2, 0.4, 0.6, 0.5);
+        assert!(c.filter(&ColorMatrix::identity()).approx_eq(&c, 1e-12));
+        assert!(c.filter(&ColorMatrix::sepia(0.0)).approx_eq(&c, 1e-12));
+        assert!(c.filter(&ColorMatrix::saturate(1.0)).approx_eq(&c, 1e-12));
+        assert!(c.filter(&ColorMatrix::hue_rotate(0.0)).approx_eq(&c, 1e-12));
+        let g = c.filter(&ColorMatrix::saturate(0.0));
+        assert!((g.red - g.green).abs() < 1e-12 && (g.green - g.blue).abs() < 1e-12);
+        assert_eq!(c.sepia().alpha, 0.5);
+        let w = Color::new(1.0, 1.0, 1.0, 1.0).filter(&ColorMatrix::luminance_to_alpha());
+        assert!((w.alpha - 1.0).abs() < 1e-3);
+        assert_eq!(w.red, 0.0);
+    }
+}
@@ -0,0 +1,503 @@
+//! Gradients built from positioned color stops
+
+use css::fmt_num;
+use Color;
+use MixSpace;
+
+/// Gradient defined by (position, Color) stops
+///
+/// Colors between stops are mixed in the gradient's MixSpace, sRGB by
+/// default.  Positions before the first stop or after the last take
+/// the color of the nearest stop.
+///
+/// ```
+/// use tint::{Color, Gradient};
+/// let g = Gradient::new(vec![(0.0, Color::from("black")),
+///                            (1.0, Color::from("white"))]);
+/// assert_eq!(g.sample(0.5), Color::from_rgb1(0.5, 0.5, 0.5));
+/// ```
+#[derive(Debug,Clone,PartialEq)]
+pub struct Gradient {
+    stops: Vec<(f64, Color)>,
+    easing: Vec<Easing>,
+    space: MixSpace,
+    interpolation: Interpolation,
+}
+
+/// Curve used to join gradient stops
+///
+///   Splines pass smoothly through the stops, avoiding visible kinks.
+///   Components, including alpha, are interpolated independently in the
+///   gradient's MixSpace, and results are not clipped
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Hash)]
+pub enum Interpolation {
+    /// Straight lines between stops, the default
+    Linear,
+    /// Catmull-Rom spline, passes through every stop
+    CatmullRom,
+    /// Uniform cubic B-spline, smoother but only passes through the end stops
+    Basis,
+}
+
+/// Easing function applied across a gradient segment
+///
+/// ```
+/// # use tint::Easing;
+/// assert_eq!(Easing::Linear.eval(0.25), 0.25);
+/// assert_eq!(Easing::Step.eval(0.99), 0.0);
+/// assert_eq!(Easing::Smoothstep.eval(0.5), 0.5);
+/// ```
+#[derive(Debug,Copy,Clone,PartialEq)]
+pub enum Easing {
+    /// Constant rate, the default
+    Linear,
+    /// Hold the color of the segment start, producing hard bands
+    Step,
+    /// Smooth start and end, 3t^2 - 2t^3
+    Smoothstep,
+    /// CSS style cubic-bezier(x1, y1, x2, y2) timing function
+    CubicBezier(f64, f64, f64, f64),
+}
+
+impl Easing {
+    /// Map t in [0, 1] onto the eased fraction
+    pub fn eval(&self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match *self {
+            Easing::Linear => t,
+            Easing::Step => if t < 1.0 { 0.0 } else { 1.0 },
+            Easing::Smoothstep => t * t * (3.0 - 2.0 * t),
+            Easing::CubicBezier(x1, y1, x2, y2) => cubic_bezier(x1, y1, x2, y2, t),
+        }
+    }
+}
+
+impl Gradient {
+    /// Create a new gradient from (position, Color) stops
+    ///
+    ///   Stops are sorted by position and must not be empty
+    pub fn new(stops: Vec<(f64, Color)>) -> Gradient {
+        assert!(!stops.is_empty(), "Gradient requires at least one stop");
+        let mut stops = stops;
+        stops.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+        let easing = vec![Easing::Linear; stops.len() - 1];
+        Gradient { stops, easing, space: MixSpace::Srgb, interpolation: Interpolation::Linear }
+    }
+    /// Create a new gradient with colors evenly spaced over [0, 1]
+    ///
+    /// ```
+    /// # use tint::{Color, Gradient};
+    /// let g = Gradient::from_colors(&[Color::from("red"), Color::from("lime"), Color::from("blue")]);
+    /// assert_eq!(g.sample(0.5), Color::from("lime"));
+    /// ```
+    pub fn from_colors(colors: &[Color]) -> Gradient {
+        let n = colors.len();
+        let stops = colors.iter().enumerate().map(|(i, &c)| {
+            let t = if n > 1 { i as f64 / (n - 1) as f64 } else { 0.0 };
+            (t, c)
+        }).collect();
+        Gradient::new(stops)
+    }
+    /// Mix colors between stops in a different color space
+    ///
+    /// ```
+    /// # use tint::{Color, Gradient, MixSpace};
+    /// let g = Gradient::from_colors(&[Color::from("black"), Color::from("white")])
+    ///     .with_space(MixSpace::LinearRgb);
+    /// assert_eq!(g.sample(0.5).to_rgb255(), (187, 187, 187));
+    /// ```
+    pub fn with_space(mut self, space: MixSpace) -> Gradient {
+        self.space = space;
+        self
+    }
+    /// Join stops with a spline rather than straight lines
+    ///
+    /// ```
+    /// # use tint::{Color, Gradient, Interpolation};
+    /// let g = Gradient::from_colors(&[Color::from("black"), Color::from("red"), Color::from("white")])
+    ///     .with_interpolation(Interpolation::CatmullRom);
+    /// assert_eq!(g.sample(0.5), Color::from("red"));
+    /// assert!(g.sample(0.25).green < 0.0);
+    /// ```
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> Gradient {
+        self.interpolation = interpolation;
+        self
+    }
+    /// Use the same easing function for every segment
+    ///
+    /// ```
+    /// # use tint::{Color, Easing, Gradient};
+    /// let g = Gradient::from_colors(&[Color::from("black"), Color::from("white")])
+    ///     .with_easing(Easing::Smoothstep);
+    /// assert!(g.sample(0.25).red < 0.25);
+    /// ```
+    pub fn with_easing(mut self, easing: Easing) -> Gradient {
+        for e in self.easing.iter_mut() {
+            *e = easing;
+        }
+        self
+    }
+    /// Set the easing function of a single segment
+    ///
+    ///   Segment i runs from stop i to stop i+1, after sorting
+    ///
+    /// ```
+    /// # use tint::{Color, Easing, Gradient};
+    /// let g = Gradient::from_colors(&[Color::from("black"), Color::from("gray"), Color::from("white")])
+    ///     .with_segment_easing(1, Easing::Step);
+    /// assert_eq!(g.sample(0.25).to_hex(), "#404040");
+    /// assert_eq!(g.sample(0.75), Color::from("gray"));
+    /// ```
+    pub fn with_segment_easing(mut self, segment: usize, easing: Easing) -> Gradient {
+        assert!(segment < self.easing.len(), "Gradient segment out of range");
+        self.easing[segment] = easing;
+        self
+    }
+    /// Banded gradient with n constant segments across the same range
+    ///
+    ///   Band colors are n evenly spaced samples, including both ends,
+    ///   as returned by samples(n)
+    ///
+    /// ```
+    /// # use tint::{Color, Gradient};
+    /// let g = Gradient::from_colors(&[Color::from("black"), Color::from("white")]).quantize(2);
+    /// assert_eq!(g.sample(0.25), Color::from("black"));
+    /// assert_eq!(g.sample(0.75), Color::from("white"));
+    /// ```
+    pub fn quantize(&self, n: usize) -> Gradient {
+        assert!(n > 0, "Gradient requires at least one band");
+        let t0 = self.stops[0].0;
+        let t1 = self.stops[self.stops.len()-1].0;
+        let w = (t1 - t0) / n as f64;
+        let mut stops = vec![];
+        for (i, c) in self.samples(n).into_iter().enumerate() {
+            stops.push((t0 + w * i as f64, c));
+            stops.push((t0 + w * (i + 1) as f64, c));
+        }
+        Gradient::new(stops).with_space(self.space)
+    }
+    /// Built-in gradient by name, ignoring case
+    ///
+    ///   Available presets are listed by Gradient::presets()
+    ///
+    /// ```
+    /// # use tint::{Color, Gradient};
+    /// let heat = Gradient::preset("heat").unwrap();
+    /// assert_eq!(heat.sample(0.0), Color::from("black"));
+    /// assert_eq!(heat.sample(1.0), Color::from("white"));
+    /// assert!(Gradient::preset("plaid").is_none());
+    /// ```
+    pub fn preset(name: &str) -> Option<Gradient> {
+        let rgb = |r: u8, g: u8, b: u8| Color::from_rgb255(r, g, b);
+        let g = match name.to_lowercase().as_str() {
+            "grayscale" | "greyscale" =>
+                Gradient::from_colors(&[rgb(0, 0, 0), rgb(255, 255, 255)]),
+            "rainbow" => {
+                let hues : Vec<_> = (0..6).map(|i| Color::from_hsv(i as f64 * 60.0, 1.0, 1.0)).collect();
+                Gradient::from_colors(&hues).with_space(MixSpace::Hsv)
+            }
+            "heat" =>
+                Gradient::new(vec![(0.0, rgb(0, 0, 0)), (0.375, rgb(255, 0, 0)),
+                                   (0.75, rgb(255, 255, 0)), (1.0, rgb(255, 255, 255))]),
+            "coolwarm" =>
+                Gradient::from_colors(&[rgb(59, 76, 192), rgb(221, 221, 221), rgb(180, 4, 38)])
+                .with_space(MixSpace::Lab),
+            "terrain" =>
+                Gradient::new(vec![(0.0, rgb(51, 51, 153)), (0.15, rgb(0, 153, 255)),
+                                   (0.25, rgb(0, 204, 102)), (0.5, rgb(255, 255, 153)),
+                                   (0.75, rgb(128, 92, 84)), (1.0, rgb(255, 255, 255))]),
+            _ => return None,
+        };
+        Some(g)
+    }
+    /// Names of the built-in gradient presets
+    pub fn presets() -> &'static [&'static str] {
+        &["grayscale", "rainbow", "heat", "coolwarm", "terrain"]
+    }
+    /// Color stops, sorted by position
+    pub fn stops(&self) -> &[(f64, Color)] {
+        &self.stops
+    }
+    /// Color at position t
+    pub fn sample(&self, t: f64) -> Color {
+        let first = self.stops[0];
+        if t <= first.0 {
+            return first.1;
+        }
+        for (i, (w, easing)) in self.stops.windows(2).zip(self.easing.iter()).enumerate() {
+            let (t0, c0) = w[0];
+            let (t1, c1) = w[1];
+            if t <= t1 {
+                if t1 - t0 <= 0.0 {
+                    return c1;
+                }
+                let f = easing.eval((t - t0) / (t1 - t0));
+                return match self.interpolation {
+                    Interpolation::Linear => c0.mix_in(&c1, self.space, f),
+                    _ => self.spline(i, f),
+                };
+            }
+        }
+        self.stops[self.stops.len()-1].1
+    }
+    /// Evaluate the spline for segment i at fraction f
+    fn spline(&self, i: usize, f: f64) -> Color {
+        let n = self.stops.len();
+        let mut pts : Vec<[f64;4]> = vec![];
+        for (k, &(_, c)) in self.stops.iter().enumerate() {
+            let v = self.space.encode(&c);
+            let mut p = [v.0, v.1, v.2, c.alpha];
+            if self.space.is_polar() && k > 0 {
+                // Unwrap hue so neighbouring stops take the shorter arc
+                let prev = pts[k-1][2];
+                p[2] += ((prev - p[2]) / 360.0).round() * 360.0;
+            }
+            pts.push(p);
+        }
+        let p1 = pts[i];
+        let p2 = pts[i+1];
+        let p0 = if i > 0 { pts[i-1] } else { extrapolate(p1, p2) };
+        let p3 = if i + 2 < n { pts[i+2] } else { extrapolate(p2, p1) };
+        let mut v = [0.0; 4];
+        for j in 0..4 {
+            v[j] = match self.interpolation {
+                Interpolation::Basis => basis(p0[j], p1[j], p2[j], p3[j], f),
+                _ => catmull_rom(p0[j], p1[j], p2[j], p3[j], f),
+            };
+        }
+        self.space.decode((v[0], v[1], v[2])).with_alpha(v[3])
+    }
+    /// Convert into a CSS linear-gradient() string running left to right
+    ///
+    ///   Stop positions are written as percentages.  Gradients that CSS
+    ///   cannot express directly, such as splines, smooth easing or HSV
+    ///   mixing, are approximated with extra stops
+    ///
+    /// ```
+    /// # use tint::{Color, Gradient, MixSpace};
+    /// let g = Gradient::from_colors(&[Color::from("red"), Color::from("blue")]);
+    /// assert_eq!(g.to_css(), "linear-gradient(90deg, #ff0000 0%, #0000ff 100%)");
+    /// let g = g.with_space(MixSpace::Oklab);
+    /// assert_eq!(g.to_css(), "linear-gradient(90deg in oklab, #ff0000 0%, #0000ff 100%)");
+    /// ```
+    pub fn to_css(&self) -> String {
+        let hex = |c: &Color| if c.alpha < 1.0 { c.to_hex_rgba() } else { c.to_hex() };
+        let exact = self.space.css_name().is_some() && self.is_piecewise_linear();
+        let stops : Vec<_> = self.linear_stops(exact).iter()
+            .map(|&(t, c)| format!("{} {}%", hex(&c), fmt_num(t * 100.0, 2)))
+            .collect();
+        let space = match self.space.css_name() {
+            Some(name) if exact && self.space != MixSpace::Srgb => format!(" in {}", name),
+            _ => String::new(),
+        };
+        format!("linear-gradient(90deg{}, {})", space, stops.join(", "))
+    }
+    /// Convert into a gnuplot `set palette defined` command
+    ///
+    ///   Alpha is ignored.  Gradients that gnuplot cannot express
+    ///   directly are approximated with extra stops
+    ///
+    /// ```
+    /// # use tint::{Color, Gradient};
+    /// let g = Gradient::new(vec![(0.0, Color::from("navy")), (0.5, Color::from("red")),
+    ///                            (1.0, Color::from("yellow"))]);
+    /// assert_eq!(g.to_gnuplot(),
+    ///            "set palette defined (0 '#000080', 0.5 '#ff0000', 1 '#ffff00')");
+    /// ```
+    pub fn to_gnuplot(&self) -> String {
+        let exact = self.space == MixSpace::Srgb && self.is_piecewise_linear();
+        let stops : Vec<_> = self.linear_stops(exact).iter()
+            .map(|&(t, c)| format!("{} '{}'", fmt_num(t, 4), c.to_hex()))
+            .collect();
+        format!("set palette defined ({})", stops.join(", "))
+    }
+    fn is_piecewise_linear(&self) -> bool {
+        self.interpolation == Interpolation::Linear &&
+            self.easing.iter().all(|e| *e == Easing::Linear || *e == Easing::Step)
+    }
+    /// Stops for a consumer that mixes linearly between them
+    ///
+    ///   If not exact, each segment is subdivided and sampled
+    fn linear_stops(&self, exact: bool) -> Vec<(f64, Color)> {
+        let n = if exact { 1 } else { 8 };
+        let mut stops = vec![];
+        for (i, w) in self.stops.windows(2).enumerate() {
+            let (t0, c0) = w[0];
+            let t1 = w[1].0;
+            if self.easing[i] == Easing::Step {
+                stops.push((t0, c0));
+                stops.push((t1, c0));
+                continue;
+            }
+            for k in 0..n {
+                let t = t0 + (t1 - t0) * k as f64 / n as f64;
+                stops.push((t, if k == 0 { c0 } else { self.sample(t) }));
+            }
+        }
+        stops.push(self.stops[self.stops.len()-1]);
+        stops
+    }
+    /// n colors sampled evenly from the first to the last stop
+    ///
+    /// ```
+    /// # use tint::{Color, Gradient};
+    /// let g = Gradient::from_colors(&[Color::from("black"), Color::from("white")]);
+    /// let hex : Vec<_> = g.samples(3).iter().map(|c| c.to_hex()).collect();
+    /// assert_eq!(hex, vec!["#000000", "#7f7f7f", "#ffffff"]);
+    /// ```
+    pub fn samples(&self, n: usize) -> Vec<Color> {
+        let t0 = self.stops[0].0;
+        let t1 = self.stops[self.stops.len()-1].0;
+        (0..n).map(|i| {
+            let f = if n > 1 { i as f64 / (n - 1) as f64 } else { 0.0 };
+            self.sample(t0 + (t1 - t0) * f)
+        }).collect()
+    }
+}
+
+/// Reflect b through a, used for missing end points
+fn extrapolate(a: [f64;4], b: [f64;4]) -> [f64;4] {
+    [2.0 * a[0] - b[0], 2.0 * a[1] - b[1], 2.0 * a[2] - b[2], 2.0 * a[3] - b[3]]
+}
+
+fn catmull_rom(p0: f64, p1: f64, p2: f64, p3: f64, t: f64) -> f64 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    0.5 * (2.0 * p1 + (p2 - p0) * t
+           + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
+           + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t3)
+}
+
+fn basis(p0: f64, p1: f64, p2: f64, p3: f64, t: f64) -> f64 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    ((1.0 - 3.0 * t + 3.0 * t2 - t3) * p0
+     + (4.0 - 6.0 * t2 + 3.0 * t3) * p1
+     + (1.0 + 3.0 * t + 3.0 * t2 - 3.0 * t3) * p2
+     + t3 * p3) / 6.0
+}
+
+/// Evaluate a cubic bezier from (0,0) to (1,1) at x, returning y
+fn cubic_bezier(x1: f64, y1: f64, x2: f64, y2: f64, x: f64) -> f64 {
+    let bez = |p1: f64, p2: f64, s: f64| {
+        3.0 * p1 * s * (1.0 - s) * (1.0 - s) + 3.0 * p2 * s * s * (1.0 - s) + s * s * s
+    };
+    // Bisection on s, x(s) is monotonic for x1, x2 in [0, 1]
+    let (mut lo, mut hi) = (0.0, 1.0);
+    for _ in 0..64 {
+        let mid = 0.5 * (lo + hi);
+        if bez(x1, x2, mid) < x {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    bez(y1, y2, 0.5 * (lo + hi))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn sample() {
+        let red = Color::from("red");
+        let blue = Color::from("blue");
+        let g = Gradient::new(vec![(0.75, blue), (0.25, red)]);
+        assert_eq!(g.stops()[0], (0.25, red));
+        assert_eq!(g.sample(0.0), red);
+        assert_eq!(g.sample(1.0), blue);
+        assert_eq!(g.sample(0.5), Color::from_rgb1(0.5, 0.0, 0.5));
+        let hard = Gradient::new(vec![(0.0, red), (0.5, red), (0.5, blue), (1.0, blue)]);
+        assert_eq!(hard.sample(0.49), red);
+        assert_eq!(hard.sample(0.5), red);
+        assert_eq!(hard.sample(0.51), blue);
+        let stepped = Gradient::from_colors(&[red, blue]).with_easing(Easing::Step);
+        assert_eq!(stepped.sample(0.9), red);
+        assert_eq!(stepped.sample(1.0), blue);
+        let one = Gradient::from_colors(&[red]);
+        assert_eq!(one.samples(2), vec![red, red]);
+    }
+    #[test]
+    fn easing() {
+        let linear = Easing::CubicBezier(0.0, 0.0, 1.0, 1.0);
+        let ease = Easing::CubicBezier(0.25, 0.1, 0.25, 1.0);
+        for i in 0..=10 {
+            let t = i as f64 / 10.0;
+            assert!((linear.eval(t) - t).abs() < 1e-9);
+        }
+        assert!(ease.eval(0.0).abs() < 1e-9);
+        assert!((ease.eval(1.0) - 1.0).abs() < 1e-9);
+        assert!((ease.eval(0.5) - 0.8024).abs() < 1e-3);
+        assert_eq!(Easing::Smoothstep.eval(-1.0), 0.0);
+    }
+    #[test]
+    fn presets() {
+        for name in Gradient::presets() {
+            let g = Gradient::preset(name).unwrap();
+            assert_eq!(Gradient::preset(&name.to_uppercase()), Some(g.clone()));
+            for c in g.samples(11) {
+                for v in c.to_rgba1().iter() {
+                    assert!(*v > -1e-9 && *v < 1.0 + 1e-9, "{} {:?}", name, c);
+                }
+            }
+        }
+        let rainbow = Gradient::preset("rainbow").unwrap();
+        assert!(rainbow.sample(0.4).approx_eq(&Color::from("lime"), 1e-9));
+    }
+    #[test]
+    fn quantize() {
+        let g = Gradient::preset("grayscale").unwrap().quantize(4);
+        let hex : Vec<_> = g.samples(8).iter().map(|c| c.to_hex()).collect();
+        assert_eq!(hex, vec!["#000000", "#000000", "#555555", "#555555",
+                             "#aaaaaa", "#aaaaaa", "#ffffff", "#ffffff"]);
+    }
+    #[test]
+    fn splines() {
+        let colors = [Color::from("navy"), Color::from("orange"), Color::from("white"), Color::from("teal")];
+        let linear = Gradient::from_colors(&colors);
+        let cr = linear.clone().with_interpolation(Interpolation::CatmullRom);
+        let bs = linear.clone().with_interpolation(Interpolation::Basis);
+        for (k, c) in colors.iter().enumerate() {
+            let t = k as f64 / 3.0;
+            assert!(cr.sample(t).approx_eq(c, 1e-9), "{}", k);
+        }
+        assert!(bs.sample(0.0).approx_eq(&colors[0], 1e-9));
+        assert!(bs.sample(1.0).approx_eq(&colors[3], 1e-9));
+        assert!(!bs.sample(1.0 / 3.0).approx_eq(&colors[1], 1e-3));
+        // Two stops: splines reduce to a straight line
+        let two = Gradient::from_colors(&colors[..2]);
+        for &i in [Interpolation::CatmullRom, Interpolation::Basis].iter() {
+            let g = two.clone().with_interpolation(i);
+            for k in 0..=4 {
+                let t = k as f64 / 4.0;
+                assert!(g.sample(t).approx_eq(&two.sample(t), 1e-9), "{:?} {}", i, t);
+            }
+        }
+        // Hue is unwrapped across 0 degrees
+        let hues = Gradient::from_colors(&[Color::from_hsv(300.0, 1.0, 1.0), Color::from_hsv(20.0, 1.0, 1.0)])
+            .with_space(MixSpace::Hsv).with_interpolation(Interpolation::CatmullRom);
+        assert!(hues.sample(0.5).approx_eq(&Color::from_hsv(340.0, 1.0, 1.0), 1e-9));
+    }
+    #[test]
+    fn css() {
+        let red = Color::from("red");
+        let blue = Color::from("blue");
+        let g = Gradient::new(vec![(0.0, red), (0.5, red.with_alpha(0.5)), (1.0, blue)])
+            .with_segment_easing(0, Easing::Step);
+        assert_eq!(g.to_css(), "linear-gradient(90deg, #ff0000 0%, #ff0000 50%, #ff00007f 50%, #0000ff 100%)");
+        let smooth = Gradient::from_colors(&[red, blue]).with_easing(Easing::Smoothstep);
+        let css = smooth.to_css();
+        assert_eq!(css.matches('%').count(), 9);
+        assert!(css.starts_with("linear-gradient(90deg, #ff0000 0%, "));
+        assert!(css.ends_with(", #0000ff 100%)"));
+    }
+    #[test]
+    fn gnuplot() {
+        let g = Gradient::from_colors(&[Color::from("black"), Color::from("white")])
+            .with_space(MixSpace::Oklab);
+        assert_eq!(g.to_gnuplot().matches('#').count(), 9);
+        let g = Gradient::preset("grayscale").unwrap().quantize(2);
+        assert_eq!(g.to_gnuplot(), "set palette defined (0 '#000000', 0.5 '#000000', \
+                                    0.5 '#ffffff', 1 '#ffffff')");
+    }
+}
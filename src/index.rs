@@ -0,0 +1,110 @@
+//! k-d tree over CIE Lab for nearest color queries
+
+use Color;
+
+type Entry = ([f64;3], String);
+
+/// Static k-d tree of named colors
+///
+///   The tree is stored implicitly: each slice is split at its median
+///   on the axis for its depth, with the median entry as the node
+pub(crate) struct KdTree {
+    entries: Vec<Entry>,
+}
+
+impl KdTree {
+    /// Build a tree from named colors
+    pub(crate) fn new<'a, I>(colors: I) -> KdTree
+        where I: Iterator<Item = (&'a String, &'a Color)>
+    {
+        let mut entries: Vec<Entry> = colors.map(|(name, c)| {
+            let (l,a,b) = c.to_lab();
+            ([l, a, b], name.clone())
+        }).collect();
+        build(&mut entries, 0);
+        KdTree { entries }
+    }
+    /// Nearest entry by Euclidean distance in Lab (CIE76), ties broken alphabetically
+    pub(crate) fn nearest(&self, color: &Color) -> Option<(String, f64)> {
+        let (l,a,b) = color.to_lab();
+        let mut best = None;
+        search(&self.entries, 0, &[l, a, b], &mut best);
+        best.map(|(name, d2)| (name.to_string(), d2.sqrt()))
+    }
+}
+
+fn build(entries: &mut [Entry], depth: usize) {
+    if entries.len() <= 1 {
+        return;
+    }
+    let axis = depth % 3;
+    entries.sort_by(|a, b| a.0[axis].partial_cmp(&b.0[axis]).unwrap());
+    let mid = entries.len() / 2;
+    let (left, right) = entries.split_at_mut(mid);
+    build(left, depth + 1);
+    build(&mut right[1..], depth + 1);
+}
+
+// map_or rather than is_none_or, which needs Rust 1.82
+#[allow(clippy::unnecessary_map_or)]
+fn search<'a>(entries: &'a [Entry], depth: usize, target: &[f64;3],
+              best: &mut Option<(&'a str, f64)>) {
+    if entries.is_empty() {
+        return;
+    }
+    let mid = entries.len() / 2;
+    let (ref point, ref name) = entries[mid];
+    let d2 = dist2(point, target);
+    let better = match *best {
+        None => true,
+        Some((bname, bd2)) => d2 < bd2 || (d2 == bd2 && name.as_str() < bname),
+    };
+    if better {
+        *best = Some((name, d2));
+    }
+    let axis = depth % 3;
+    let diff = target[axis] - point[axis];
+    let (near, far) = if diff < 0.0 {
+        (&entries[..mid], &entries[mid+1..])
+    } else {
+        (&entries[mid+1..], &entries[..mid])
+    };
+    search(near, depth + 1, target, best);
+    // Equal distances may lie across the plane, so search it when touching
+    if best.map_or(true, |(_, bd2)| diff * diff <= bd2) {
+        search(far, depth + 1, target, best);
+    }
+}
+
+fn dist2(a: &[f64;3], b: &[f64;3]) -> f64 {
+    (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    #[test]
+    fn matches_linear_scan() {
+        let mut m = HashMap::new();
+        for i in 0..200 {
+            let x = i as f64;
+            let c = Color::new((x * 0.37).fract(), (x * 0.61).fract(), (x * 0.83).fract(), 1.0);
+            m.insert(format!("c{}", i), c);
+        }
+        let tree = KdTree::new(m.iter());
+        for i in 0..50 {
+            let x = i as f64 + 0.5;
+            let q = Color::new((x * 0.13).fract(), (x * 0.29).fract(), (x * 0.71).fract(), 1.0);
+            let (l,a,b) = q.to_lab();
+            let linear = m.iter()
+                .map(|(name, c)| (name.clone(), dist2(&[l, a, b], &{ let v = c.to_lab(); [v.0, v.1, v.2] })))
+                .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap().then_with(|| a.0.cmp(&b.0)))
+                .unwrap();
+            let (name, d) = tree.nearest(&q).unwrap();
+            assert_eq!(name, linear.0);
+            assert!((d - linear.1.sqrt()).abs() < 1e-12);
+        }
+        assert!(KdTree::new(HashMap::new().iter()).nearest(&Color::default()).is_none());
+    }
+}
@@ -9,6 +9,7 @@
 //! let green = Color::from("00ff00");
 //! let green = Color::from("#00ff00");
 //! let green = Color::from("#00FF00");
+//! let green = Color::from("rgb(0, 255, 0)");
 //! let green = Color::from((0,255,0));
 //! let green = Color::from([0.,1.,0.]);
 //! let green = Color::from(vec![0.,1.,0.]);
@@ -33,20 +34,133 @@
 //! https://www.w3.org/TR/css3-color/#svg-color
 //! ## XKCD Colors
 //! https://xkcd.com/color/rgb/
+//!
+//! # Integer conversions
+//!   Components are stored as f64 in [0,1].  Conversions to integer
+//!   components, e.g. to_rgb255(), to_rgba8(), to_rgb65535() and
+//!   to_hex(), scale by the largest integer value and truncate, so
+//!   0.5 becomes 127 as a u8 and 32767 as a u16
 
 
 #[macro_use]
 extern crate lazy_static;
+#[cfg(feature = "approx")]
+#[cfg_attr(test, macro_use)]
+extern crate approx;
+#[cfg(feature = "bevy")]
+extern crate bevy_color;
+#[cfg(feature = "colored")]
+extern crate colored;
+#[cfg(feature = "crossterm")]
+extern crate crossterm;
+#[cfg(feature = "egui")]
+extern crate ecolor;
+#[cfg(feature = "embedded-graphics")]
+extern crate embedded_graphics_core;
+#[cfg(feature = "glam")]
+extern crate glam;
+#[cfg(feature = "nalgebra")]
+extern crate nalgebra;
+#[cfg(feature = "plotters")]
+extern crate plotters;
+#[cfg(feature = "rand")]
+extern crate rand;
+#[cfg(feature = "ratatui")]
+extern crate ratatui;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "termcolor")]
+extern crate termcolor;
 
 use std::collections::HashMap;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 use std::io::Cursor;
 use std::fmt;
 use std::fs::File;
 use std::io::BufReader;
 use std::io::BufRead;
 use std::path::Path;
-
+use std::str::FromStr;
+use std::convert::TryFrom;
+
+use index::KdTree;
+
+mod adapt;
+mod adjust;
+mod ansi;
+#[cfg(feature = "bevy")]
+mod bevy;
+mod blend;
+mod brewer;
+mod color32;
+mod color8;
+#[cfg(feature = "colored")]
+mod colored_color;
+mod colormap;
+mod compare;
+mod composite;
+mod contrast;
+#[cfg(feature = "crossterm")]
+mod crossterm_color;
+mod css;
+mod curve;
+mod cvd;
+mod difference;
+#[cfg(feature = "egui")]
+mod egui;
+#[cfg(feature = "embedded-graphics")]
+mod embedded;
+mod error;
+mod filter;
+mod gradient;
+mod index;
+mod literal;
+mod mix;
+mod ops;
+mod pack;
+mod parse;
+#[cfg(feature = "plotters")]
+mod plot;
+mod ramp;
+#[cfg(feature = "rand")]
+mod random;
+#[cfg(feature = "ratatui")]
+mod ratatui_color;
+mod simd;
+mod space;
+mod spectrum;
+mod temperature;
+#[cfg(feature = "termcolor")]
+mod termcolor_color;
+mod theme;
+#[cfg(any(feature = "glam", feature = "nalgebra"))]
+mod vector;
+pub mod batch;
+pub mod quantize;
+#[cfg(feature = "serde")]
+pub mod serialize;
+pub use adapt::Illuminant;
+pub use adjust::GrayscaleMethod;
+pub use ansi::swatch;
+pub use blend::BlendMode;
+pub use brewer::{brewer, brewer_classes, brewer_palettes, PaletteType};
+pub use color32::ColorF32;
+pub use color8::Color8;
+pub use colormap::{colormap, colormap_from_json, colormaps};
+pub use composite::{flatten, AlphaMode, CompositeOp};
+pub use contrast::{contrast_matrix, ContrastPair, TextSize};
+pub use curve::Curve;
+pub use cvd::{cvd_check, daltonize_palette, Cvd, CvdReport};
+pub use difference::Cie94;
+pub use error::ParseColorError;
+pub use filter::ColorMatrix;
+pub use gradient::{Easing, Gradient, Interpolation};
+pub use mix::{HueInterpolation, MixSpace};
+pub use space::ColorSpace;
+pub use temperature::Warmth;
+pub use theme::Base16;
 
 pub type Colour = Color;
 
@@ -71,7 +185,29 @@ impl Color {
     /// let fushcia = Color::new(1.0, 0.0, 1.0, 1.0);
     /// ```
     pub fn new(red: f64, green: f64, blue: f64, alpha: f64) -> Color {
-        Color { red: red, green: green, blue: blue, alpha: alpha }
+        Color { red, green, blue, alpha }
+    }
+    /// Return a copy of the Color with a new red component
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let c = Color::default().with_red(1.0).with_alpha(0.5);
+    /// assert_eq!(c, Color::new(1.0, 0.0, 0.0, 0.5));
+    /// ```
+    pub fn with_red(self, red: f64) -> Color {
+        Color { red, ..self }
+    }
+    /// Return a copy of the Color with a new green component
+    pub fn with_green(self, green: f64) -> Color {
+        Color { green, ..self }
+    }
+    /// Return a copy of the Color with a new blue component
+    pub fn with_blue(self, blue: f64) -> Color {
+        Color { blue, ..self }
+    }
+    /// Return a copy of the Color with a new alpha component
+    pub fn with_alpha(self, alpha: f64) -> Color {
+        Color { alpha, ..self }
     }
 
     // RGB 1.0
@@ -148,21 +284,129 @@ impl Color {
         let b = (self.blue  * 255.0) as u8;
         (r,g,b)
     }
+    /// Convert color to [u8;4], red, green, blue, alpha
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let purple = Color::new(1.0, 0.0, 1.0, 0.5);
+    /// assert_eq!(purple.to_rgba8(), [255, 0, 255, 127]);
+    /// ```
+    pub fn to_rgba8(&self) -> [u8;4] {
+        let (r,g,b) = self.to_rgb255();
+        [r, g, b, (self.alpha * 255.0) as u8]
+    }
+    /// Convert color to [f64;4], red, green, blue, alpha
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let purple = Color::new(1.0, 0.0, 1.0, 0.5);
+    /// assert_eq!(purple.to_rgba1(), [1.0, 0.0, 1.0, 0.5]);
+    /// ```
+    pub fn to_rgba1(&self) -> [f64;4] {
+        [self.red, self.green, self.blue, self.alpha]
+    }
+    /// Iterate over red, green, blue and alpha components
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let purple = Color::new(1.0, 0.0, 1.0, 0.5);
+    /// let bytes : Vec<u8> = purple.channels().map(|v| (v * 255.0) as u8).collect();
+    /// assert_eq!(bytes, vec![255, 0, 255, 127]);
+    /// ```
+    pub fn channels(&self) -> std::array::IntoIter<f64, 4> {
+        IntoIterator::into_iter(self.to_rgba1())
+    }
+    /// Create new Color from RGB u16 values [0 .. 65535]
+    ///   alpha value set to 1.0
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let purple = Color::from_rgb65535(65535, 0, 65535);
+    /// assert_eq!(purple.to_rgb255(), (255,0,255));
+    /// ```
+    pub fn from_rgb65535(red: u16, green: u16, blue: u16) -> Color {
+        Color::from_rgb1((red as f64)/65535.,
+                         (green as f64)/65535.,
+                         (blue as f64)/65535.)
+    }
+    /// Convert color to (u16,u16,u16)
+    ///
+    ///   Components are truncated, as in to_rgb255()
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let purple = Color::new(1.0, 0.0, 1.0, 1.0);
+    /// assert_eq!(purple.to_rgb65535(), (65535,0,65535));
+    /// ```
+    pub fn to_rgb65535(&self) -> (u16,u16,u16) {
+        let r = (self.red   * 65535.0) as u16;
+        let g = (self.green * 65535.0) as u16;
+        let b = (self.blue  * 65535.0) as u16;
+        (r,g,b)
+    }
 
     // HEX
     /// Create new Color from Hex String
     ///
+    ///   Panics if the string is not a valid hex color,
+    ///   see try_from_hex() for a non-panicking version
+    ///
     /// ```
     /// # use tint::Color;
     /// let facade = Color::from_hex("#facade");
     /// assert_eq!(facade.to_rgb255(), (250, 202, 222));
     /// ```
     pub fn from_hex(hex: &str) -> Color {
-        let n = if hex.chars().nth(0).unwrap() == '#' { 1 } else { 0 };
-        let r = u8::from_str_radix(&hex[n+0..n+2],16).unwrap();
-        let g = u8::from_str_radix(&hex[n+2..n+4],16).unwrap();
-        let b = u8::from_str_radix(&hex[n+4..n+6],16).unwrap();
-        Color::from_rgb255(r,g,b)
+        match Color::try_from_hex(hex) {
+            Ok(c) => c,
+            Err(e) => panic!("Invalid hex color {:?}: {}", hex, e),
+        }
+    }
+    /// Create new Color from Hex String, returning an error on failure
+    ///
+    ///   The leading '#' is optional.  Supported forms follow CSS:
+    ///      RGB, RGBA, RRGGBB and RRGGBBAA
+    ///   Alpha is set to 1.0 if not present
+    ///
+    /// ```
+    /// # use tint::{Color, ParseColorError};
+    /// let facade = Color::try_from_hex("#facade");
+    /// assert_eq!(facade, Ok(Color::from_rgb255(250, 202, 222)));
+    ///
+    /// let fab = Color::try_from_hex("#fab").unwrap();
+    /// assert_eq!(fab.to_hex(), "#ffaabb");
+    ///
+    /// let half = Color::try_from_hex("#ff000080").unwrap();
+    /// assert_eq!(half.alpha, 128.0/255.0);
+    /// let half = Color::try_from_hex("#f008").unwrap();
+    /// assert_eq!(half.alpha, 136.0/255.0);
+    ///
+    /// assert_eq!(Color::try_from_hex("#12345g"), Err(ParseColorError::InvalidHexDigit('g')));
+    /// assert_eq!(Color::try_from_hex("#12345"), Err(ParseColorError::InvalidHexLength(5)));
+    /// assert_eq!(Color::try_from_hex(""), Err(ParseColorError::Empty));
+    /// ```
+    pub fn try_from_hex(hex: &str) -> Result<Color, ParseColorError> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        if digits.is_empty() {
+            return Err(ParseColorError::Empty);
+        }
+        if let Some(c) = digits.chars().find(|c| !c.is_ascii_hexdigit()) {
+            return Err(ParseColorError::InvalidHexDigit(c));
+        }
+        let v : Vec<u8> = match digits.len() {
+            3 | 4 => digits.chars()
+                .map(|c| c.to_digit(16).unwrap() as u8 * 17)
+                .collect(),
+            6 | 8 => (0..digits.len()).step_by(2)
+                .map(|i| u8::from_str_radix(&digits[i..i+2], 16).unwrap())
+                .collect(),
+            n => return Err(ParseColorError::InvalidHexLength(n)),
+        };
+        let mut c = Color::from_rgb255(v[0], v[1], v[2]);
+        if v.len() == 4 {
+            c.alpha = v[3] as f64 / 255.0;
+        }
+        Ok(c)
     }
     /// Convert Color into Hex String
     ///
@@ -175,6 +419,92 @@ impl Color {
         let (r,g,b) = self.to_rgb255();
         format!("#{:02x}{:02x}{:02x}", r,g,b)
     }
+    /// Create new Color from an RGBA Hex String, as used on the web
+    ///
+    ///   Alpha is the last component, #RRGGBBAA or #RGBA.
+    ///   Alpha is set to 1.0 if not present
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let c = Color::from_hex_rgba("#ff000080").unwrap();
+    /// assert_eq!(c.to_rgb255(), (255, 0, 0));
+    /// assert_eq!(c.alpha, 128.0/255.0);
+    /// ```
+    pub fn from_hex_rgba(hex: &str) -> Result<Color, ParseColorError> {
+        Color::try_from_hex(hex)
+    }
+    /// Create new Color from an ARGB Hex String, as used on Android
+    ///
+    ///   Alpha is the first component, #AARRGGBB or #ARGB.
+    ///   Alpha is set to 1.0 if not present
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let c = Color::from_hex_argb("#80ff0000").unwrap();
+    /// assert_eq!(c.to_rgb255(), (255, 0, 0));
+    /// assert_eq!(c.alpha, 128.0/255.0);
+    /// ```
+    pub fn from_hex_argb(hex: &str) -> Result<Color, ParseColorError> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        let n = match digits.len() {
+            4 => 1,
+            8 => 2,
+            _ => return Color::try_from_hex(digits),
+        };
+        if !digits.is_char_boundary(n) {
+            return Color::try_from_hex(digits);
+        }
+        let rgba = format!("{}{}", &digits[n..], &digits[..n]);
+        Color::try_from_hex(&rgba)
+    }
+    /// Convert Color into an RGBA Hex String, #RRGGBBAA
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let c = Color::new(1.0, 0.0, 0.0, 0.5);
+    /// assert_eq!(c.to_hex_rgba(), "#ff00007f");
+    /// ```
+    pub fn to_hex_rgba(&self) -> String {
+        let (r,g,b) = self.to_rgb255();
+        let a = (self.alpha * 255.0) as u8;
+        format!("#{:02x}{:02x}{:02x}{:02x}", r,g,b,a)
+    }
+    /// Convert Color into an ARGB Hex String, #AARRGGBB
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let c = Color::new(1.0, 0.0, 0.0, 0.5);
+    /// assert_eq!(c.to_hex_argb(), "#7fff0000");
+    /// ```
+    pub fn to_hex_argb(&self) -> String {
+        let (r,g,b) = self.to_rgb255();
+        let a = (self.alpha * 255.0) as u8;
+        format!("#{:02x}{:02x}{:02x}{:02x}", a,r,g,b)
+    }
+    /// Convert Color into an 8 digit Hex String including alpha, #RRGGBBAA
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let c = Color::new(1.0, 0.0, 0.0, 0.5);
+    /// assert_eq!(c.to_hex8(), "#ff00007f");
+    /// ```
+    pub fn to_hex8(&self) -> String {
+        self.to_hex_with(true, false)
+    }
+    /// Convert Color into a Hex String with optional alpha and case
+    ///
+    ///   With alpha the output is #RRGGBBAA, otherwise #RRGGBB
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let coffee = Color::from_rgb255(192, 255, 238);
+    /// assert_eq!(coffee.to_hex_with(false, true), "#C0FFEE");
+    /// assert_eq!(coffee.to_hex_with(true, true), "#C0FFEEFF");
+    /// ```
+    pub fn to_hex_with(&self, alpha: bool, uppercase: bool) -> String {
+        let hex = if alpha { self.to_hex_rgba() } else { self.to_hex() };
+        if uppercase { hex.to_uppercase() } else { hex }
+    }
     //pub fn from_hexs(hex: &str) -> Vec<Color> {
     //    hex.split(',').map(|x| Color::from_hex(x)).collect()
     //}
@@ -199,79 +529,266 @@ impl Color {
     /// assert_eq!(avocado.to_rgb255(), (135, 169, 34));
     /// ```
     pub fn name(name: &str) -> Option<Color> {
-        match COLOR_MAP.lock().unwrap().get(name) {
-            Some(&c) => Some(c.clone()),
-            None => None,
+        COLOR_MAP.lock().unwrap().get(name).cloned()
+    }
+    /// Describe Color using its name, if an exact named color exists
+    ///
+    ///   Names are matched on red, green and blue only.  If several
+    ///   names match, the alphabetically first is used
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let red = Color::from_rgb255(255, 0, 0);
+    /// assert_eq!(red.describe(), "Color(red, #ff0000, a=1.0)");
+    /// let c = Color::new(0.1, 0.2, 0.3, 0.5);
+    /// assert_eq!(c.describe(), "Color(#19334c, a=0.5)");
+    /// ```
+    pub fn describe(&self) -> String {
+        match lookup_name(self) {
+            Some(name) => format!("Color({}, {}, a={:?})", name, self.to_hex(), self.alpha),
+            None => format!("Color({}, a={:?})", self.to_hex(), self.alpha),
         }
     }
+    /// Find the closest named color and its CIEDE2000 difference
+    ///
+    ///   All loaded names are searched, including XKCD colors if
+    ///   loaded.  Ties are broken alphabetically
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let (name, de) = Color::from_rgb255(250, 10, 5).nearest_name();
+    /// assert_eq!(name, "red");
+    /// assert!(de > 0.0 && de < 3.0);
+    /// ```
+    pub fn nearest_name(&self) -> (String, f64) {
+        COLOR_MAP.lock().unwrap().iter()
+            .map(|(name, c)| (name, self.delta_e2000(c)))
+            .min_by(by_difference)
+            .map(|(name, de)| (name.clone(), de))
+            .expect("named color database is empty")
+    }
+    /// Find the n closest named colors, sorted by CIEDE2000 difference
+    ///
+    ///   Fewer than n names are returned if fewer are loaded
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let names = Color::from_rgb255(250, 128, 114).closest_names(3);
+    /// assert_eq!(names.len(), 3);
+    /// assert_eq!(names[0], ("salmon".to_string(), 0.0));
+    /// assert!(names[1].1 <= names[2].1);
+    /// ```
+    pub fn closest_names(&self, n: usize) -> Vec<(String, f64)> {
+        closest_names(self, n)
+    }
+    /// Find the closest named color by CIE76 distance using a k-d tree index
+    ///
+    ///   Much faster than nearest_name() for many lookups, but ranks by
+    ///   the Euclidean distance in Lab (CIE76), which is returned, rather
+    ///   than CIEDE2000, so the two may pick different names.
+    ///   The index is built on first use and rebuilt after new names
+    ///   are loaded
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let (name, de) = Color::from_rgb255(250, 10, 5).nearest_name_cie76();
+    /// assert_eq!(name, "red");
+    /// assert!(de > 0.0 && de < 5.0);
+    /// ```
+    pub fn nearest_name_cie76(&self) -> (String, f64) {
+        name_index().nearest(self).expect("named color database is empty")
+    }
 
     // HSV
     /// Convert Color to HSV
     pub fn to_hsv(&self) -> (f64,f64,f64) {
         rgb2hsv(self.red, self.green, self.blue)
     }
-    /// Create new Color from HSV
+    /// Create new Color from HSV, hue, saturation, value
     ///   alpha value set to 1.0
-    pub fn from_hsv(&self) -> Color {
-        let (r,g,b) = hsv2rgb(self.red, self.green, self.blue);
-        Color::new(r,g,b,1.0)
+    ///
+    ///   h : [0, 360],  s : [0, 1],  v : [0, 1]
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let c = Color::from_hsv(300.0, 1.0, 1.0);
+    /// assert_eq!(c.to_rgb255(), (255, 0, 255));
+    /// ```
+    pub fn from_hsv(h: f64, s: f64, v: f64) -> Color {
+        Color::from_hsva(h, s, v, 1.0)
+    }
+    /// Create new Color from HSV and alpha
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let c = Color::from_hsva(120.0, 1.0, 1.0, 0.5);
+    /// assert_eq!(c, Color::new(0.0, 1.0, 0.0, 0.5));
+    /// ```
+    pub fn from_hsva(h: f64, s: f64, v: f64, alpha: f64) -> Color {
+        let (r,g,b) = hsv2rgb(h.rem_euclid(360.0), s, v);
+        Color::new(r,g,b,alpha)
     }
     // HSL
-    /// Convert Color to HSL
+    /// Convert Color to HSL, hue, saturation, lightness
+    ///
+    ///   h : [0, 360],  s : [0, 1],  l : [0, 1]
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let c = Color::from_rgb1(0.0, 0.0, 1.0);
+    /// assert_eq!(c.to_hsl(), (240.0, 1.0, 0.5));
+    /// ```
     pub fn to_hsl(&self) -> (f64,f64,f64) {
-        rgb2hsl(self.red, self.green, self.blue)
+        let (h,s,l) = rgb2hsl(self.red, self.green, self.blue);
+        (h * 360.0, s, l)
     }
-    /// Create new Color from HSL
+    /// Create new Color from HSL, hue, saturation, lightness
     ///   alpha value set to 1.0
-    pub fn from_hsl(&self) -> Color {
-        let (r,g,b) = hsl2rgb(self.red, self.green, self.blue);
+    ///
+    ///   h : [0, 360],  s : [0, 1],  l : [0, 1]
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let c = Color::from_hsl(240.0, 1.0, 0.5);
+    /// assert_eq!(c.to_rgb255(), (0, 0, 255));
+    /// ```
+    pub fn from_hsl(h: f64, s: f64, l: f64) -> Color {
+        let (r,g,b) = hsl2rgb(h.rem_euclid(360.0) / 360.0, s, l);
         Color::new(r,g,b,1.0)
     }
+    /// Return a copy of the Color with a new HSL hue, in degrees
+    ///
+    ///   Alpha is unchanged
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let red = Color::from_rgb255(255, 0, 0);
+    /// assert_eq!(red.with_hue(240.0).to_rgb255(), (0, 0, 255));
+    /// ```
+    pub fn with_hue(&self, hue: f64) -> Color {
+        let (_,s,l) = self.to_hsl();
+        self.with_hsl(hue.rem_euclid(360.0), s, l)
+    }
+    /// Return a copy of the Color with a new HSL saturation [0,1]
+    ///
+    ///   Alpha is unchanged
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let red = Color::from_rgb255(255, 0, 0);
+    /// assert_eq!(red.with_saturation(0.0).to_hex(), "#7f7f7f");
+    /// ```
+    pub fn with_saturation(&self, saturation: f64) -> Color {
+        let (h,_,l) = self.to_hsl();
+        self.with_hsl(h, saturation, l)
+    }
+    /// Return a copy of the Color with a new HSL lightness [0,1]
+    ///
+    ///   Alpha is unchanged
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let red = Color::from_rgb255(255, 0, 0);
+    /// assert_eq!(red.with_lightness(0.25).to_rgb255(), (127, 0, 0));
+    /// ```
+    pub fn with_lightness(&self, lightness: f64) -> Color {
+        let (h,s,_) = self.to_hsl();
+        self.with_hsl(h, s, lightness)
+    }
+    fn with_hsl(&self, h: f64, s: f64, l: f64) -> Color {
+        Color::from_hsl(h, s.clamp(0.0, 1.0), l.clamp(0.0, 1.0)).with_alpha(self.alpha)
+    }
     // YIQ
     /// Convert Color to YIQ
     pub fn to_yiq(&self) -> (f64,f64,f64) {
         rgb2yiq(self.red, self.green, self.blue)
     }
-    /// Create new Color from YIQ
+    /// Create new Color from YIQ, luma and chrominance
     ///   alpha value set to 1.0
-    pub fn from_yiq(&self) -> Color {
-        let (r,g,b) = yiq2rgb(self.red, self.green, self.blue);
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let c = Color::from_rgb255(250, 202, 222);
+    /// let (y, i, q) = c.to_yiq();
+    /// assert!(Color::from_yiq(y, i, q).approx_eq(&c, 1e-12));
+    /// ```
+    pub fn from_yiq(y: f64, i: f64, q: f64) -> Color {
+        let (r,g,b) = yiq2rgb(y, i, q);
+        Color::new(r,g,b,1.0)
+    }
+    // HWB
+    /// Convert Color to HWB, hue, whiteness, blackness
+    ///
+    ///   h : [0, 360],  w : [0, 1],  b : [0, 1]
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let c = Color::from_rgb1(1.0, 0.5, 0.5);
+    /// assert_eq!(c.to_hwb(), (0.0, 0.5, 0.0));
+    /// ```
+    pub fn to_hwb(&self) -> (f64,f64,f64) {
+        rgb2hwb(self.red, self.green, self.blue)
+    }
+    /// Create new Color from HWB, hue, whiteness, blackness
+    ///   alpha value set to 1.0
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let c = Color::from_hwb(120.0, 0.0, 0.5);
+    /// assert_eq!(c.to_rgb1(), (0.0, 0.5, 0.0));
+    /// ```
+    pub fn from_hwb(h: f64, w: f64, b: f64) -> Color {
+        let (r,g,b) = hwb2rgb(h, w, b);
         Color::new(r,g,b,1.0)
     }
 }
 
 // Strings
 
-/// Convert from named color or a hex string
+/// Parse a named color, a CSS function or a hex string
+///
+/// See Color::parse()
+///
+/// ```
+/// # use tint::{Color, ParseColorError};
+/// let green : Color = "#00ff00".parse().unwrap();
+/// assert_eq!(green, Color::from_rgb255(0, 255, 0));
+/// let red : Color = "red".parse().unwrap();
+/// assert_eq!(red, Color::from_rgb255(255, 0, 0));
+/// let red : Color = "rgb(255, 0, 0)".parse().unwrap();
+/// assert_eq!(red, Color::from_rgb255(255, 0, 0));
+/// assert_eq!("#00ff0".parse::<Color>(), Err(ParseColorError::InvalidHexLength(5)));
+/// ```
+impl FromStr for Color {
+    type Err = ParseColorError;
+    fn from_str(s: &str) -> Result<Color, ParseColorError> {
+        Color::parse(s)
+    }
+}
+/// Convert from named color, a CSS function or a hex string
 ///
 /// This may fail
 impl From<String> for Color {
     fn from(s: String) -> Color {
-        match Color::name(&s) {
-            None => Color::from_hex(&s),
-            Some(c) => c
-        }
+        Color::from(s.as_str())
     }
 }
-/// Convert from named color or a hex string
+/// Convert from named color, a CSS function or a hex string
 ///
 /// This may fail
 impl <'a> From<&'a String> for Color {
     fn from(s: &'a String) -> Color {
-        match Color::name(s) {
-            None => Color::from_hex(s),
-            Some(c) => c
-        }
+        Color::from(s.as_str())
     }
 }
-/// Convert from named color or a hex string
+/// Convert from named color, a CSS function or a hex string
 ///
 /// This may fail
 impl <'a> From<&'a str> for Color {
     fn from(s: &'a str) -> Color {
-        match Color::name(s) {
-            None => Color::from_hex(s),
-            Some(c) => c
+        match s.parse() {
+            Ok(c) => c,
+            Err(e) => panic!("Invalid color {:?}: {}", s, e),
         }
     }
 }
@@ -295,6 +812,18 @@ impl From<(f32,f32,f32)> for Color {
         Color::from_rgb1(c.0 as f64, c.1 as f64, c.2 as f64)
     }
 }
+/// Convert from a u8 quadruple, red, green, blue, alpha
+impl From<(u8,u8,u8,u8)> for Color {
+    fn from(c: (u8,u8,u8,u8)) -> Color {
+        Color::from([c.0, c.1, c.2, c.3])
+    }
+}
+/// Convert from a f64 quadruple, red, green, blue, alpha
+impl From<(f64,f64,f64,f64)> for Color {
+    fn from(c: (f64,f64,f64,f64)) -> Color {
+        Color::new(c.0, c.1, c.2, c.3)
+    }
+}
 
 // Arrays
 /// Convert from a u8 triple, red, green, blue
@@ -333,32 +862,98 @@ impl From<[f32;3]> for Color {
         Color::new(c[0] as f64, c[1] as f64, c[2] as f64, 1.0)
     }
 }
+/// Convert from a u8 quadruple, red, green, blue, alpha
+impl From<[u8;4]> for Color {
+    fn from(c: [u8;4]) -> Color {
+        let mut color = Color::from_rgb255(c[0], c[1], c[2]);
+        color.alpha = c[3] as f64 / 255.0;
+        color
+    }
+}
+/// Convert from a f64 quadruple, red, green, blue, alpha
+impl From<[f64;4]> for Color {
+    fn from(c: [f64;4]) -> Color {
+        Color::new(c[0], c[1], c[2], c[3])
+    }
+}
+/// Convert from a u16 quadruple, red, green, blue, alpha
+impl From<[u16;4]> for Color {
+    fn from(c: [u16;4]) -> Color {
+        let mut color = Color::from_rgb65535(c[0], c[1], c[2]);
+        color.alpha = c[3] as f64 / 65535.0;
+        color
+    }
+}
+/// Convert into a u16 quadruple, red, green, blue, alpha
+impl From<Color> for [u16;4] {
+    fn from(c: Color) -> [u16;4] {
+        let (r,g,b) = c.to_rgb65535();
+        [r, g, b, (c.alpha * 65535.0) as u16]
+    }
+}
+
+// Slices
+/// Convert from a f64 slice, red, green, blue, maybe alpha
+///
+///   Fails with InvalidArgumentCount if the slice is not three
+///   or four components long
+///
+/// ```
+/// # use tint::{Color, ParseColorError};
+/// use std::convert::TryFrom;
+/// let v = vec![1.0, 0.0, 1.0];
+/// assert_eq!(Color::try_from(&v[..]), Ok(Color::new(1.0, 0.0, 1.0, 1.0)));
+/// assert_eq!(Color::try_from(&v[..2]), Err(ParseColorError::InvalidArgumentCount(2)));
+/// ```
+impl <'a> TryFrom<&'a [f64]> for Color {
+    type Error = ParseColorError;
+    fn try_from(c: &'a [f64]) -> Result<Color, ParseColorError> {
+        match c.len() {
+            3 => Ok(Color::new(c[0], c[1], c[2], 1.0)),
+            4 => Ok(Color::new(c[0], c[1], c[2], c[3])),
+            n => Err(ParseColorError::InvalidArgumentCount(n)),
+        }
+    }
+}
+/// Convert from a f32 slice, red, green, blue, maybe alpha
+///
+///   Fails with InvalidArgumentCount if the slice is not three
+///   or four components long
+impl <'a> TryFrom<&'a [f32]> for Color {
+    type Error = ParseColorError;
+    fn try_from(c: &'a [f32]) -> Result<Color, ParseColorError> {
+        let c64 : Vec<_> = c.iter().map(|x| *x as f64).collect();
+        Color::try_from(&c64[..])
+    }
+}
 
 // Vecs
 /// Convert from a f64 Vec, red, green, blue, maybe alpha
 ///
-/// This may fail
+/// Panics if the Vec is not three or four components long,
+/// prefer `Color::try_from(&v[..])`
 impl <'a> From<&'a Vec<f64>> for Color {
     fn from(c: &'a Vec<f64>) -> Color {
-        match c.len() {
-            3 => Color::new(c[0], c[1], c[2], 1.0),
-            4 => Color::new(c[0], c[1], c[2], c[3]),
-            _ => panic!("Expected three or four color components"),
+        match Color::try_from(&c[..]) {
+            Ok(c) => c,
+            Err(_) => panic!("Expected three or four color components"),
         }
     }
 }
 /// Convert from a f32 Vec, red, green, blue, maybe alpha
 ///
-/// This may fail
+/// Panics if the Vec is not three or four components long,
+/// prefer `Color::try_from(&v[..])`
 impl <'a> From<&'a Vec<f32>> for Color {
     fn from(c: &'a Vec<f32>) -> Color {
-        let c64 : Vec<_> = c.into_iter().map(|x| *x as f64).collect();
+        let c64 : Vec<_> = c.iter().map(|x| *x as f64).collect();
         Color::from(&c64)
     }
 }
-/// Convert from a f32 Vec, red, green, blue, maybe alpha
+/// Convert from a f64 Vec, red, green, blue, maybe alpha
 ///
-/// This may fail
+/// Panics if the Vec is not three or four components long,
+/// prefer `Color::try_from(&v[..])`
 impl From<Vec<f64>> for Color {
     fn from(c: Vec<f64>) -> Color {
         Color::from(&c)
@@ -366,7 +961,8 @@ impl From<Vec<f64>> for Color {
 }
 /// Convert from a f32 Vec, red, green, blue, maybe alpha
 ///
-/// This may fail
+/// Panics if the Vec is not three or four components long,
+/// prefer `Color::try_from(&v[..])`
 impl From<Vec<f32>> for Color {
     fn from(c: Vec<f32>) -> Color {
         let c64 : Vec<_> = c.into_iter().map(|x| x as f64).collect();
@@ -383,9 +979,64 @@ impl PartialEq for Color {
     }
 }
 
+/// Opaque black
+impl Default for Color {
+    fn default() -> Color {
+        Color::new(0.0, 0.0, 0.0, 1.0)
+    }
+}
+
+/// Format Color as a tuple of red, green, blue and alpha values
+///
+///   The alternate flag `{:#}` formats the Color as a CSS rgb() or
+///   rgba() function with 8-bit channels
+///
+/// ```
+/// # use tint::Color;
+/// let red = Color::new(1.0, 0.0, 0.0, 1.0);
+/// assert_eq!(format!("{}", red), "(1.000, 0.000, 0.000, 1.000)");
+/// assert_eq!(format!("{:#}", red), "rgb(255, 0, 0)");
+/// let red = Color::new(1.0, 0.0, 0.0, 0.5);
+/// assert_eq!(format!("{:#}", red), "rgba(255, 0, 0, 0.5)");
+/// ```
 impl fmt::Display for Color {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "({:5.3}, {:5.3}, {:5.3}, {:5.3})", self.red, self.green, self.blue, self.alpha)
+        if f.alternate() {
+            let (r,g,b) = self.to_rgb255();
+            if self.alpha < 1.0 {
+                write!(f, "rgba({}, {}, {}, {})", r, g, b, css::fmt_num(self.alpha, 3))
+            } else {
+                write!(f, "rgb({}, {}, {})", r, g, b)
+            }
+        } else {
+            write!(f, "({:5.3}, {:5.3}, {:5.3}, {:5.3})", self.red, self.green, self.blue, self.alpha)
+        }
+    }
+}
+
+/// Format Color as a lowercase Hex String, #rrggbb
+///
+/// ```
+/// # use tint::Color;
+/// let coffee = Color::from_rgb255(192, 255, 238);
+/// assert_eq!(format!("{:x}", coffee), "#c0ffee");
+/// ```
+impl fmt::LowerHex for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(&self.to_hex())
+    }
+}
+
+/// Format Color as an uppercase Hex String, #RRGGBB
+///
+/// ```
+/// # use tint::Color;
+/// let coffee = Color::from_rgb255(192, 255, 238);
+/// assert_eq!(format!("{:X}", coffee), "#C0FFEE");
+/// ```
+impl fmt::UpperHex for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(&self.to_hex_with(false, true))
     }
 }
 
@@ -416,7 +1067,7 @@ fn parse_name_hex(line: &str) -> Option<(String, Color)> {
         let name = vals[0].to_owned();
         let hex = vals[1];
         if hex.len() == 6 {
-            return Some((name, Color::from_hex(hex)));
+            return Color::try_from_hex(hex).ok().map(|c| (name, c));
         }
     }
     None
@@ -429,7 +1080,6 @@ fn parse_name_hex(line: &str) -> Option<(String, Color)> {
 ///      name  #hex-value
 ///      r255 g255 b255 name
 ///   Lines beginning with # are ignored
-
 pub fn read_buffer<T>(buf: T) -> Vec<(String, Color)>
     where T: BufRead
 {
@@ -470,6 +1120,7 @@ pub fn load_rgb_buffer<T>(buf: T)
             continue;
         }
         COLOR_MAP.lock().unwrap().insert(name, color);
+        *NAME_INDEX.lock().unwrap() = None;
     }
 }
 /// Load a file into the existing Named Color database.
@@ -483,19 +1134,26 @@ pub fn load_rgb_file<P>(file: P)
     load_rgb_buffer(fp);
 }
 
-lazy_static! {
-    static ref COLOR_MAP: Mutex<HashMap<String, Color>> = {
-        let mut m : HashMap<String, Color> = HashMap::new();
-        for s in [COLORS_BASIC, COLORS_EXTENDED].iter() {
-            for (ref xname, color) in read_buffer( Cursor::new( s ) ).into_iter() {
-                let name = xname.to_lowercase();
-                if ! m.contains_key(&name) {
-                    m.insert(name, color);
-                }
-            }
+fn w3c_colors() -> HashMap<String, Color> {
+    let mut m : HashMap<String, Color> = HashMap::new();
+    for s in [COLORS_BASIC, COLORS_EXTENDED].iter() {
+        for (ref xname, color) in read_buffer( Cursor::new( s ) ).into_iter() {
+            let name = xname.to_lowercase();
+            m.entry(name).or_insert(color);
         }
-        Mutex::new(m)
-    };
+    }
+    m
+}
+
+lazy_static! {
+    static ref COLOR_MAP: Mutex<HashMap<String, Color>> = Mutex::new(w3c_colors());
+    static ref CSS_COLORS: HashMap<String, Color> = w3c_colors();
+    static ref NAME_INDEX: Mutex<Option<Arc<KdTree>>> = Mutex::new(None);
+}
+/// Current k-d tree index of named colors, built on first use
+pub(crate) fn name_index() -> Arc<KdTree> {
+    let mut index = NAME_INDEX.lock().unwrap();
+    index.get_or_insert_with(|| Arc::new(KdTree::new(COLOR_MAP.lock().unwrap().iter()))).clone()
 }
 /// Load colors from the XKCD Color Database
 pub fn xkcd() {
@@ -508,6 +1166,46 @@ pub fn names() -> Vec<String> {
     map.keys().cloned().collect()
 }
 
+/// Find the alphabetically first name exactly matching red, green and blue
+pub(crate) fn lookup_name(color: &Color) -> Option<String> {
+    exact_name(COLOR_MAP.lock().unwrap().iter(), color)
+}
+
+/// As lookup_name(), but only W3C names, whatever names have been loaded
+#[cfg(feature = "serde")]
+pub(crate) fn lookup_css_name(color: &Color) -> Option<String> {
+    exact_name(CSS_COLORS.iter(), color)
+}
+
+fn exact_name<'a, I>(names: I, color: &Color) -> Option<String>
+    where I: Iterator<Item = (&'a String, &'a Color)>
+{
+    names.filter(|&(_, c)| c.red == color.red && c.green == color.green && c.blue == color.blue)
+        .map(|(name, _)| name.clone())
+        .min()
+}
+
+/// Find the n closest named colors by CIEDE2000, ties broken alphabetically
+fn closest_names(color: &Color, n: usize) -> Vec<(String, f64)> {
+    let mut v: Vec<_> = COLOR_MAP.lock().unwrap().iter()
+        .map(|(name, c)| (name.clone(), color.delta_e2000(c)))
+        .collect();
+    if n < v.len() {
+        if n == 0 {
+            return Vec::new();
+        }
+        v.select_nth_unstable_by(n - 1, by_difference);
+        v.truncate(n);
+    }
+    v.sort_by(by_difference);
+    v
+}
+
+// Order by difference, then name; total_cmp so NaN cannot panic
+fn by_difference<S: AsRef<str>>(a: &(S, f64), b: &(S, f64)) -> std::cmp::Ordering {
+    a.1.total_cmp(&b.1).then_with(|| a.0.as_ref().cmp(b.0.as_ref()))
+}
+
 fn cmp3(a: (f64,f64,f64), b:(f64,f64,f64)) -> std::cmp::Ordering {
     if a.0 > b.0 {
         return std::cmp::Ordering::Greater;
@@ -524,7 +1222,7 @@ fn cmp3(a: (f64,f64,f64), b:(f64,f64,f64)) -> std::cmp::Ordering {
     } else if a.2 < b.2 {
         return std::cmp::Ordering::Less;
     }
-    return std::cmp::Ordering::Equal;
+    std::cmp::Ordering::Equal
 }
 
 /// Compare Colors by red, then green, then blue
@@ -611,7 +1309,7 @@ fn hsv2rgb(h: f64, s: f64, v: f64) -> (f64, f64, f64) {
     if hh >= 360.0 {
         hh = 0.0;
     }
-    hh = hh / 60.0;
+    hh /= 60.0;
     let i = hh.floor() as u64;
     let ff = hh - i as f64;
     let p = v * (1.0 - s);
@@ -652,7 +1350,7 @@ fn rgb2hsl(r: f64, g: f64, b: f64) -> (f64, f64, f64) {
         } else {
             0.0
         };
-    h = h / 6.0;
+    h /= 6.0;
     (h,s,l)
 }
 
@@ -673,7 +1371,7 @@ fn hue2rgb(p: f64, q: f64, t: f64) -> f64 {
     if tt < 2./3. {
         return p + (q - p) * (2./3. - tt) * 6.0;
     }
-    return p;
+    p
 }
 
 fn hsl2rgb(h: f64, s: f64, l: f64) -> (f64, f64, f64) {
@@ -693,11 +1391,28 @@ fn hsl2rgb(h: f64, s: f64, l: f64) -> (f64, f64, f64) {
     (r,g,b)
 }
 
+// https://www.w3.org/TR/css-color-4/#hwb-to-rgb
+fn rgb2hwb(r: f64, g: f64, b: f64) -> (f64, f64, f64) {
+    let (h, _, v) = rgb2hsv(r, g, b);
+    (h, fmin(&[r,g,b]), 1.0 - v)
+}
+
+fn hwb2rgb(h: f64, w: f64, b: f64) -> (f64, f64, f64) {
+    if w + b >= 1.0 {
+        let gray = w / (w + b);
+        return (gray, gray, gray);
+    }
+    let v = 1.0 - b;
+    let s = 1.0 - w / v;
+    hsv2rgb(h.rem_euclid(360.0), s, v)
+}
+
 //include!("extended.rs");
 
-static COLORS_BASIC:    &'static str = include_str!("w3c_basic.txt");
-static COLORS_EXTENDED: &'static str = include_str!("w3c_extended.txt");
-static COLORS_XKCD:     &'static str = include_str!("xkcd.txt");
+// const rather than static so Color::from_literal() can read them
+const COLORS_BASIC:     &str = include_str!("w3c_basic.txt");
+const COLORS_EXTENDED:  &str = include_str!("w3c_extended.txt");
+static COLORS_XKCD:     &str = include_str!("xkcd.txt");
 
 
 #[cfg(test)]
@@ -717,7 +1432,7 @@ mod tests {
         for name in ["black","silver","gray","white","maroon", "red","purple",
                      "fuchsia","green","lime","olive","yellow","navy","blue",
                      "teal","aqua"].iter() {
-            assert_eq!(Color::name(name).is_some(), true);
+            assert!(Color::name(name).is_some());
         }
     }
 
@@ -777,20 +1492,43 @@ mod tests {
                          "turquoise","violet","wheat","white","whitesmoke","yellow",
                          "yellowgreen"];
         for name in ext_names.iter() {
-            assert_eq!(Color::name(name).is_some(), true);
+            assert!(Color::name(name).is_some());
         }
     }
     #[test]
     fn bad_name() {
-        assert_eq!(Color::name("asdf").is_none(), true);
+        assert!(Color::name("asdf").is_none());
     }
     #[test]
     fn test_xkcd() {
         xkcd();
-        assert_eq!(Color::name("toxic green").is_some(), true);
-        assert_eq!(Color::name("blood").is_some(), true);
-        assert_eq!(Color::name("vomit").is_some(), true);
-        assert_eq!(Color::name("baby poop").is_some(), true);
+        assert!(Color::name("toxic green").is_some());
+        assert!(Color::name("blood").is_some());
+        assert!(Color::name("vomit").is_some());
+        assert!(Color::name("baby poop").is_some());
+    }
+    #[test]
+    fn test_nearest_name() {
+        assert_eq!(Color::from_hex("#00ffff").nearest_name(), ("aqua".to_string(), 0.0));
+        let (name, de) = Color::from_hex("#f0f0f0").nearest_name();
+        assert!(de < 2.0, "{} {}", name, de);
+        assert_eq!(Color::from_hex("#00ffff").nearest_name_cie76(), ("aqua".to_string(), 0.0));
+        let (fast, _) = Color::from_hex("#123456").nearest_name_cie76();
+        assert!(Color::name(&fast).is_some());
+        let (name, _) = Color::new(f64::NAN, 0.0, 0.0, 1.0).nearest_name();
+        assert!(Color::name(&name).is_some());
+    }
+    #[test]
+    fn test_closest_names() {
+        let c = Color::from_hex("#ff7f50");
+        assert!(c.closest_names(0).is_empty());
+        let v = c.closest_names(5);
+        assert_eq!(v[0].0, "coral");
+        assert!(v.windows(2).all(|w| w[0].1 <= w[1].1));
+        assert_eq!(c.closest_names(1)[0], c.nearest_name());
+        let all = c.closest_names(usize::MAX);
+        assert_eq!(v[..], all[..5]);
+        assert_eq!(Color::new(f64::NAN, 0.0, 0.0, 1.0).closest_names(3).len(), 3);
     }
     #[test]
     fn test_from() {
@@ -810,6 +1548,14 @@ mod tests {
         assert_eq!(Color::from("red".to_string()), red);
     }
 
+    #[test]
+    fn test_from_rgba() {
+        let c = Color::new(1.0, 0.0, 1.0, 0.5);
+        assert_eq!(Color::from((1.0, 0.0, 1.0, 0.5)), c);
+        assert_eq!(Color::from([1.0, 0.0, 1.0, 0.5]), c);
+        assert_eq!(Color::from((255u8, 0, 255, 51)), c.with_alpha(0.2));
+        assert_eq!(Color::from([255u8, 0, 255, 51]).to_rgba8(), [255, 0, 255, 51]);
+    }
     #[test]
     fn test_into() {
         let red = Color::name("red").unwrap();
@@ -819,9 +1565,38 @@ mod tests {
         assert_eq!(red, (1.0,0.0,0.0).into());
     }
     #[test]
+    fn test_from_str() {
+        let red = Color::name("red").unwrap();
+        assert_eq!("red".parse::<Color>(), Ok(red));
+        assert_eq!("#ff0000".parse::<Color>(), Ok(red));
+        assert_eq!("ff0000".parse::<Color>(), Ok(red));
+        assert!("not a color".parse::<Color>().is_err());
+    }
+    #[test]
+    fn test_try_from() {
+        let v = [0.5f32, 0.25, 1.0, 0.5];
+        assert_eq!(Color::try_from(&v[..]), Ok(Color::new(0.5, 0.25, 1.0, 0.5)));
+        assert_eq!(Color::try_from(&v[..3]), Ok(Color::new(0.5, 0.25, 1.0, 1.0)));
+        assert_eq!(Color::try_from(&[0.0f64; 5][..]), Err(ParseColorError::InvalidArgumentCount(5)));
+        assert_eq!(Color::try_from(&[0.0f32; 0][..]), Err(ParseColorError::InvalidArgumentCount(0)));
+    }
+    #[test]
+    fn test_rgb65535() {
+        for v in (0..=65535_u16).step_by(7) {
+            let c = Color::from_rgb65535(v, 65535 - v, v / 2);
+            assert_eq!(c.to_rgb65535(), (v, 65535 - v, v / 2));
+            let q : [u16;4] = Color::from([v, 0, 65535, v]).into();
+            assert_eq!(q, [v, 0, 65535, v]);
+        }
+    }
+    #[test]
     fn test_display() {
         let red = Color::name("red").unwrap();
         assert_eq!(format!("{}", red), "(1.000, 0.000, 0.000, 1.000)");
+        assert_eq!(format!("{:#}", red), "rgb(255, 0, 0)");
+        assert_eq!(format!("{:x}", red), "#ff0000");
+        assert_eq!(format!("{:X}", red), "#FF0000");
+        assert_eq!(format!("{:>9x}", red), "  #ff0000");
     }
 
     fn assert_tol(a: (f64,f64,f64), b: (f64,f64,f64), tol: f64) {
@@ -863,6 +1638,22 @@ mod tests {
         }
     }
     #[test]
+    fn polar_round_trip() {
+        for r in (0..256).step_by(15) {
+            for g in (0..256).step_by(15) {
+                for b in (0..256).step_by(15) {
+                    let c = Color::from_rgb255(r as u8, g as u8, b as u8);
+                    let (h,s,v) = c.to_hsv();
+                    assert!(Color::from_hsv(h,s,v).approx_eq(&c, 1e-12), "hsv {:?}", c);
+                    let (h,s,l) = c.to_hsl();
+                    assert!(Color::from_hsl(h,s,l).approx_eq(&c, 1e-12), "hsl {:?}", c);
+                    let (h,w,k) = c.to_hwb();
+                    assert!(Color::from_hwb(h,w,k).approx_eq(&c, 1e-12), "hwb {:?}", c);
+                }
+            }
+        }
+    }
+    #[test]
     #[ignore]
     fn hsl() {
         for r in 0..256 {
@@ -893,7 +1684,7 @@ mod tests {
         assert_eq!(rgb2hsv(1.,0.,1.), (300.,1.,1.));
         assert_eq!(rgb2hsv(0.,1.,1.), (180.,1.,1.));
 
-        assert_eq!(rgb2hsv(1.0,0.0,std::f64::EPSILON), (360.,1.0,1.0));
+        assert_eq!(rgb2hsv(1.0,0.0,f64::EPSILON), (360.,1.0,1.0));
 
         assert_eq!(rgb2hsv(1./255.,1./255.,2./255.), (240.,0.5, 2./255.));
         assert_eq!(hsv2rgb(240.,0.5,2./255.), (1./255.,1./255., 2./255.));
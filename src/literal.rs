@@ -0,0 +1,166 @@
+//! Compile time color literals
+
+use Color;
+use COLORS_BASIC;
+use COLORS_EXTENDED;
+
+/// Create a Color from a hex string or W3C color name at compile time
+///
+///   Invalid hex strings and unknown names are compile errors when
+///   the macro is used, rather than runtime panics
+///
+/// ```
+/// #[macro_use] extern crate tint;
+/// # fn main() {
+/// let orange = color!("#ff8800");
+/// assert_eq!(orange.to_rgb255(), (255, 136, 0));
+/// let blue = color!("cornflowerblue");
+/// assert_eq!(blue.to_hex(), "#6495ed");
+/// const RED: tint::Color = color!("red");
+/// assert_eq!(RED.to_hex(), "#ff0000");
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// #[macro_use] extern crate tint;
+/// # fn main() {
+/// let typo = color!("#ff88zz");
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// #[macro_use] extern crate tint;
+/// # fn main() {
+/// let trailing = color!("#ff0000 garbage");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! color {
+    ($s:expr) => {{
+        const C: $crate::Color = $crate::Color::from_literal($s);
+        C
+    }};
+}
+
+impl Color {
+    /// Create new Color from a hex string or W3C color name in a const context
+    ///
+    ///   Hex strings must start with '#' and have 3, 4, 6 or 8 digits.
+    ///   Names are case insensitive.  Panics if the string is invalid,
+    ///   which is a compile error when evaluated as a constant; see
+    ///   the color! macro
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// const TEAL: Color = Color::from_literal("Teal");
+    /// assert_eq!(TEAL.to_hex(), "#008080");
+    /// ```
+    pub const fn from_literal(s: &str) -> Color {
+        let b = s.as_bytes();
+        if !b.is_empty() && b[0] == b'#' {
+            return hex_literal(b, 1, b.len());
+        }
+        match find_name(COLORS_BASIC.as_bytes(), b) {
+            Some(c) => c,
+            None => match find_name(COLORS_EXTENDED.as_bytes(), b) {
+                Some(c) => c,
+                None => panic!("unknown color name"),
+            },
+        }
+    }
+}
+
+const fn hex_digit(c: u8) -> u8 {
+    match c {
+        b'0'..=b'9' => c - b'0',
+        b'a'..=b'f' => c - b'a' + 10,
+        b'A'..=b'F' => c - b'A' + 10,
+        _ => panic!("invalid hex digit"),
+    }
+}
+
+/// Parse hex digits in b[start..end]
+const fn hex_literal(b: &[u8], start: usize, end: usize) -> Color {
+    let mut v = [0u8, 0, 0, 255];
+    match end - start {
+        3 | 4 => {
+            let mut i = 0;
+            while i < end - start {
+                v[i] = hex_digit(b[start + i]) * 17;
+                i += 1;
+            }
+        }
+        6 | 8 => {
+            let mut i = 0;
+            while i < (end - start) / 2 {
+                v[i] = hex_digit(b[start + 2*i]) * 16 + hex_digit(b[start + 2*i + 1]);
+                i += 1;
+            }
+        }
+        _ => panic!("invalid number of hex digits"),
+    }
+    Color {
+        red:   v[0] as f64 / 255.0,
+        green: v[1] as f64 / 255.0,
+        blue:  v[2] as f64 / 255.0,
+        alpha: v[3] as f64 / 255.0,
+    }
+}
+
+/// Find a name in a "name #rrggbb" table, ignoring case and comment lines
+const fn find_name(table: &[u8], name: &[u8]) -> Option<Color> {
+    let mut i = 0;
+    while i < table.len() {
+        let line = i;
+        while i < table.len() && table[i] != b'\n' {
+            i += 1;
+        }
+        let eol = i;
+        i += 1;
+        if table[line] == b'#' {
+            continue;
+        }
+        let mut j = 0;
+        while line + j < eol && j < name.len() &&
+            table[line + j].eq_ignore_ascii_case(&name[j]) {
+            j += 1;
+        }
+        if j != name.len() || line + j >= eol || !table[line + j].is_ascii_whitespace() {
+            continue;
+        }
+        let mut k = line + j;
+        while k < eol && table[k] != b'#' {
+            k += 1;
+        }
+        if k < eol {
+            let mut end = k + 1;
+            while end < eol && !table[end].is_ascii_whitespace() {
+                end += 1;
+            }
+            return Some(hex_literal(table, k + 1, end));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use CSS_COLORS;
+    #[test]
+    fn literals() {
+        // The W3C tables only, as other tests may load XKCD names
+        for (name, c) in CSS_COLORS.iter() {
+            assert_eq!(Color::from_literal(name), *c, "{}", name);
+        }
+        assert_eq!(Color::from_literal("#c0ffee"), Color::from_hex("#c0ffee"));
+        assert_eq!(Color::from_literal("#C0FFEE80"), Color::from_hex("#c0ffee80"));
+        assert_eq!(Color::from_literal("#fab"), Color::from_hex("#ffaabb"));
+        assert_eq!(Color::from_literal("RED"), Color::from_hex("#ff0000"));
+    }
+    #[test]
+    #[should_panic]
+    fn trailing_text() {
+        Color::from_literal("#ff0000 garbage");
+    }
+}
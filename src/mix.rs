@@ -0,0 +1,279 @@
+//! Color mixing and interpolation
+
+use Color;
+
+/// Color space used for mixing and interpolation
+///
+///   Perceptual spaces like Oklab avoid the dark, muddy midpoints
+///   produced by mixing gamma encoded sRGB
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Hash)]
+pub enum MixSpace {
+    /// Gamma encoded sRGB, the space of Color itself
+    Srgb,
+    /// Linear light sRGB
+    LinearRgb,
+    /// CIE Lab, D50 white point
+    Lab,
+    /// CIE LCH, polar
+    Lch,
+    /// Oklab
+    Oklab,
+    /// Oklch, polar
+    Oklch,
+    /// HSL, polar
+    Hsl,
+    /// HSV, polar
+    Hsv,
+}
+
+/// Direction taken around the hue circle when mixing in a polar space
+///
+///   Matches the hue interpolation methods of CSS Color 4
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Hash)]
+pub enum HueInterpolation {
+    /// Take the shorter arc, the default
+    Shorter,
+    /// Take the longer arc
+    Longer,
+    /// Hue always increases from the first color to the second
+    Increasing,
+    /// Hue always decreases from the first color to the second
+    Decreasing,
+}
+
+impl MixSpace {
+    pub(crate) fn is_polar(&self) -> bool {
+        matches!(*self, MixSpace::Lch | MixSpace::Oklch | MixSpace::Hsl | MixSpace::Hsv)
+    }
+    /// Name used by CSS color interpolation, if CSS supports the space
+    pub(crate) fn css_name(&self) -> Option<&'static str> {
+        match *self {
+            MixSpace::Srgb => Some("srgb"),
+            MixSpace::LinearRgb => Some("srgb-linear"),
+            MixSpace::Lab => Some("lab"),
+            MixSpace::Lch => Some("lch"),
+            MixSpace::Oklab => Some("oklab"),
+            MixSpace::Oklch => Some("oklch"),
+            MixSpace::Hsl => Some("hsl"),
+            MixSpace::Hsv => None,
+        }
+    }
+    /// Polar spaces are ordered as lightness, chroma, hue in degrees
+    pub(crate) fn encode(self, c: &Color) -> (f64,f64,f64) {
+        match self {
+            MixSpace::Srgb => c.to_rgb1(),
+            MixSpace::LinearRgb => c.to_linear_rgb(),
+            MixSpace::Lab => c.to_lab(),
+            MixSpace::Lch => c.to_lch(),
+            MixSpace::Oklab => c.to_oklab(),
+            MixSpace::Oklch => c.to_oklch(),
+            MixSpace::Hsl => {
+                let (h,s,l) = c.to_hsl();
+                (l, s, h)
+            }
+            MixSpace::Hsv => {
+                let (h,s,v) = c.to_hsv();
+                (v, s, h)
+            }
+        }
+    }
+    pub(crate) fn decode(self, v: (f64,f64,f64)) -> Color {
+        match self {
+            MixSpace::Srgb => Color::from_rgb1(v.0, v.1, v.2),
+            MixSpace::LinearRgb => Color::from_linear_rgb(v.0, v.1, v.2),
+            MixSpace::Lab => Color::from_lab(v.0, v.1, v.2),
+            MixSpace::Lch => Color::from_lch(v.0, v.1, v.2),
+            MixSpace::Oklab => Color::from_oklab(v.0, v.1, v.2),
+            MixSpace::Oklch => Color::from_oklch(v.0, v.1, v.2),
+            MixSpace::Hsl => Color::from_hsl(v.2, v.1, v.0),
+            MixSpace::Hsv => Color::from_hsv(v.2, v.1, v.0),
+        }
+    }
+}
+
+impl Color {
+    /// Linearly interpolate between two colors
+    ///
+    ///   Red, green, blue and alpha are interpolated independently,
+    ///   t = 0 returns self and t = 1 returns other
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let black = Color::new(0.0, 0.0, 0.0, 1.0);
+    /// let white = Color::new(1.0, 1.0, 1.0, 0.0);
+    /// assert_eq!(black.lerp(&white, 0.25), Color::new(0.25, 0.25, 0.25, 0.75));
+    /// ```
+    pub fn lerp(&self, other: &Color, t: f64) -> Color {
+        Color::new(lerp(self.red, other.red, t),
+                   lerp(self.green, other.green, t),
+                   lerp(self.blue, other.blue, t),
+                   lerp(self.alpha, other.alpha, t))
+    }
+    /// Mix two colors, t = 0 returns self and t = 1 returns other
+    ///
+    ///   Colors are interpolated using premultiplied alpha, so a fully
+    ///   transparent color contributes no hue to the mix, as in CSS
+    ///   color-mix()
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let red = Color::new(1.0, 0.0, 0.0, 1.0);
+    /// let clear = Color::new(0.0, 0.0, 1.0, 0.0);
+    /// let c = red.mix(&clear, 0.5);
+    /// assert_eq!(c, Color::new(1.0, 0.0, 0.0, 0.5));
+    /// let blue = Color::new(0.0, 0.0, 1.0, 1.0);
+    /// assert_eq!(red.mix(&blue, 0.5), Color::new(0.5, 0.0, 0.5, 1.0));
+    /// ```
+    pub fn mix(&self, other: &Color, t: f64) -> Color {
+        let alpha = lerp(self.alpha, other.alpha, t);
+        if alpha <= 0.0 {
+            return self.lerp(other, t);
+        }
+        let pre = |a: f64, b: f64| lerp(a * self.alpha, b * other.alpha, t) / alpha;
+        Color::new(pre(self.red, other.red),
+                   pre(self.green, other.green),
+                   pre(self.blue, other.blue),
+                   alpha)
+    }
+    /// Mix two colors in a chosen color space, t = 0 returns self and t = 1 returns other
+    ///
+    ///   Alpha is handled as in mix().  For polar spaces the hue of an
+    ///   achromatic color is ignored and hue follows the shorter arc.
+    ///   Results outside of the sRGB gamut are not clipped
+    ///
+    /// ```
+    /// # use tint::{Color, MixSpace};
+    /// let black = Color::from_rgb255(0, 0, 0);
+    /// let white = Color::from_rgb255(255, 255, 255);
+    /// let srgb = black.mix_in(&white, MixSpace::Srgb, 0.5);
+    /// let linear = black.mix_in(&white, MixSpace::LinearRgb, 0.5);
+    /// assert_eq!(srgb.to_rgb255(), (127, 127, 127));
+    /// assert_eq!(linear.to_rgb255(), (187, 187, 187));
+    /// ```
+    pub fn mix_in(&self, other: &Color, space: MixSpace, t: f64) -> Color {
+        self.mix_in_hue(other, space, HueInterpolation::Shorter, t)
+    }
+    /// Mix two colors in a chosen color space with a hue interpolation method
+    ///
+    ///   The hue interpolation method is ignored for non-polar spaces
+    ///
+    /// ```
+    /// # use tint::{Color, MixSpace, HueInterpolation};
+    /// let red = Color::from_rgb255(255, 0, 0);
+    /// let blue = Color::from_rgb255(0, 0, 255);
+    /// let magenta = red.mix_in_hue(&blue, MixSpace::Hsl, HueInterpolation::Shorter, 0.5);
+    /// assert!(magenta.approx_eq(&Color::from_rgb255(255, 0, 255), 1e-9));
+    /// let green = red.mix_in_hue(&blue, MixSpace::Hsl, HueInterpolation::Longer, 0.5);
+    /// assert!(green.approx_eq(&Color::from_rgb255(0, 255, 0), 1e-9));
+    /// ```
+    pub fn mix_in_hue(&self, other: &Color, space: MixSpace, hue: HueInterpolation, t: f64) -> Color {
+        let a = space.encode(self);
+        let b = space.encode(other);
+        let alpha = lerp(self.alpha, other.alpha, t);
+        let (wa, wb) = if alpha > 0.0 {
+            (self.alpha / alpha, other.alpha / alpha)
+        } else {
+            (1.0, 1.0)
+        };
+        let pre = |x: f64, y: f64| lerp(x * wa, y * wb, t);
+        let v = if space.is_polar() {
+            (pre(a.0, b.0), pre(a.1, b.1), mix_hue(a, b, hue, t))
+        } else {
+            (pre(a.0, b.0), pre(a.1, b.1), pre(a.2, b.2))
+        };
+        space.decode(v).with_alpha(alpha)
+    }
+}
+
+/// Interpolate hue in degrees, ignoring the hue of achromatic colors
+fn mix_hue(a: (f64,f64,f64), b: (f64,f64,f64), method: HueInterpolation, t: f64) -> f64 {
+    let eps = 1e-4 * a.0.abs().max(b.0.abs()).max(1.0);
+    let (ha, hb) = match (a.1 < eps, b.1 < eps) {
+        (true, false) => (b.2, b.2),
+        (false, true) => (a.2, a.2),
+        _ => (a.2, b.2),
+    };
+    let mut d = hb.rem_euclid(360.0) - ha.rem_euclid(360.0);
+    match method {
+        HueInterpolation::Shorter => {
+            if d > 180.0 {
+                d -= 360.0;
+            } else if d < -180.0 {
+                d += 360.0;
+            }
+        }
+        HueInterpolation::Longer => {
+            if d > 0.0 && d < 180.0 {
+                d -= 360.0;
+            } else if d > -180.0 && d <= 0.0 {
+                d += 360.0;
+            }
+        }
+        HueInterpolation::Increasing => {
+            if d < 0.0 {
+                d += 360.0;
+            }
+        }
+        HueInterpolation::Decreasing => {
+            if d > 0.0 {
+                d -= 360.0;
+            }
+        }
+    }
+    (ha + d * t).rem_euclid(360.0)
+}
+
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    a + (b - a) * t
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn mix() {
+        let a = Color::new(0.2, 0.4, 0.6, 1.0);
+        let b = Color::new(0.6, 0.2, 0.0, 1.0);
+        assert_eq!(a.mix(&b, 0.0), a);
+        assert_eq!(a.mix(&b, 1.0), b);
+        assert!(a.mix(&b, 0.5).approx_eq(&a.lerp(&b, 0.5), 1e-12));
+        let clear = Color::new(0.0, 0.0, 0.0, 0.0);
+        assert_eq!(clear.mix(&clear, 0.5), clear);
+        let half = Color::new(1.0, 1.0, 1.0, 0.5);
+        let c = a.mix(&half, 0.5);
+        assert!((c.alpha - 0.75).abs() < 1e-12);
+        assert!((c.red - (0.2 * 0.5 + 0.25) / 0.75).abs() < 1e-12);
+    }
+    #[test]
+    fn hue_methods() {
+        let a = (1.0, 1.0, 30.0);
+        let b = (1.0, 1.0, 90.0);
+        assert_eq!(mix_hue(a, b, HueInterpolation::Shorter, 0.5), 60.0);
+        assert_eq!(mix_hue(a, b, HueInterpolation::Longer, 0.5), 240.0);
+        assert_eq!(mix_hue(a, b, HueInterpolation::Increasing, 0.5), 60.0);
+        assert_eq!(mix_hue(a, b, HueInterpolation::Decreasing, 0.5), 240.0);
+        assert_eq!(mix_hue(b, a, HueInterpolation::Increasing, 0.5), 240.0);
+        assert_eq!(mix_hue(b, a, HueInterpolation::Decreasing, 0.5), 60.0);
+    }
+    #[test]
+    fn mix_in() {
+        let a = Color::from_rgb255(250, 202, 222);
+        let b = Color::from_rgb255(20, 90, 40);
+        for space in [MixSpace::Srgb, MixSpace::LinearRgb, MixSpace::Lab,
+                      MixSpace::Lch, MixSpace::Oklab, MixSpace::Oklch,
+                      MixSpace::Hsl, MixSpace::Hsv].iter() {
+            assert!(a.mix_in(&b, *space, 0.0).approx_eq(&a, 1e-6), "{:?}", space);
+            assert!(a.mix_in(&b, *space, 1.0).approx_eq(&b, 1e-6), "{:?}", space);
+        }
+        assert!(a.mix_in(&b, MixSpace::Srgb, 0.3).approx_eq(&a.mix(&b, 0.3), 1e-12));
+        let s = HueInterpolation::Shorter;
+        assert_eq!(mix_hue((1.0, 1.0, 350.0), (1.0, 1.0, 10.0), s, 0.5), 0.0);
+        assert_eq!(mix_hue((1.0, 1.0, 10.0), (1.0, 1.0, 350.0), s, 0.25), 5.0);
+        assert_eq!(mix_hue((1.0, 0.0, 0.0), (1.0, 1.0, 90.0), s, 0.5), 90.0);
+        // White to blue keeps the hue of blue in Oklch
+        let white = Color::from_rgb255(255, 255, 255);
+        let blue = Color::from_rgb255(0, 0, 255);
+        let (_, _, h) = white.mix_in(&blue, MixSpace::Oklch, 0.5).to_oklch();
+        assert!((h - blue.to_oklch().2).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,100 @@
+//! Arithmetic operators
+//!
+//! Operators act component-wise on red, green and blue.  Alpha is taken
+//! from the left-hand Color and results are not clipped to [0,1].
+//!
+//! ```
+//! use tint::Color;
+//! let a = Color::from_rgb1(0.2, 0.4, 0.6);
+//! let b = Color::from_rgb1(0.4, 0.0, 0.2);
+//! let mid = (a + b) / 2.0;
+//! assert!(mid.approx_eq(&Color::from_rgb1(0.3, 0.2, 0.4), 1e-12));
+//! let dim = a * 0.5;
+//! assert!(dim.approx_eq(&Color::from_rgb1(0.1, 0.2, 0.3), 1e-12));
+//! ```
+
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+
+use Color;
+
+impl Add for Color {
+    type Output = Color;
+    fn add(self, other: Color) -> Color {
+        Color::new(self.red + other.red, self.green + other.green,
+                   self.blue + other.blue, self.alpha)
+    }
+}
+
+impl Sub for Color {
+    type Output = Color;
+    fn sub(self, other: Color) -> Color {
+        Color::new(self.red - other.red, self.green - other.green,
+                   self.blue - other.blue, self.alpha)
+    }
+}
+
+impl Mul<f64> for Color {
+    type Output = Color;
+    fn mul(self, s: f64) -> Color {
+        Color::new(self.red * s, self.green * s, self.blue * s, self.alpha)
+    }
+}
+
+impl Mul<Color> for f64 {
+    type Output = Color;
+    fn mul(self, c: Color) -> Color {
+        c * self
+    }
+}
+
+impl Div<f64> for Color {
+    type Output = Color;
+    fn div(self, s: f64) -> Color {
+        Color::new(self.red / s, self.green / s, self.blue / s, self.alpha)
+    }
+}
+
+impl AddAssign for Color {
+    fn add_assign(&mut self, other: Color) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Color {
+    fn sub_assign(&mut self, other: Color) {
+        *self = *self - other;
+    }
+}
+
+impl MulAssign<f64> for Color {
+    fn mul_assign(&mut self, s: f64) {
+        *self = *self * s;
+    }
+}
+
+impl DivAssign<f64> for Color {
+    fn div_assign(&mut self, s: f64) {
+        *self = *self / s;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn ops() {
+        let a = Color::new(0.5, 0.25, 1.0, 0.5);
+        let b = Color::new(0.25, 0.25, 0.5, 1.0);
+        assert_eq!(a + b, Color::new(0.75, 0.5, 1.5, 0.5));
+        assert_eq!(a - b, Color::new(0.25, 0.0, 0.5, 0.5));
+        assert_eq!(a * 2.0, Color::new(1.0, 0.5, 2.0, 0.5));
+        assert_eq!(2.0 * a, a * 2.0);
+        assert_eq!(a / 2.0, Color::new(0.25, 0.125, 0.5, 0.5));
+        let mut c = a;
+        c += b;
+        c -= b;
+        c *= 4.0;
+        c /= 4.0;
+        assert_eq!(c, a);
+    }
+}
@@ -0,0 +1,170 @@
+//! Packed integer pixel formats
+
+use Color;
+
+impl Color {
+    /// Create new Color from a packed 32-bit RGBA value, 0xRRGGBBAA
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let c = Color::from_u32_rgba(0xff000080);
+    /// assert_eq!(c.to_rgb255(), (255, 0, 0));
+    /// assert_eq!(c.alpha, 128.0/255.0);
+    /// ```
+    pub fn from_u32_rgba(v: u32) -> Color {
+        let [r, g, b, a] = v.to_be_bytes();
+        Color::from([r, g, b, a])
+    }
+    /// Create new Color from a packed 32-bit ARGB value, 0xAARRGGBB
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let c = Color::from_u32_argb(0x80ff0000);
+    /// assert_eq!(c.to_rgb255(), (255, 0, 0));
+    /// assert_eq!(c.alpha, 128.0/255.0);
+    /// ```
+    pub fn from_u32_argb(v: u32) -> Color {
+        let [a, r, g, b] = v.to_be_bytes();
+        Color::from([r, g, b, a])
+    }
+    /// Convert Color into a packed 32-bit RGBA value, 0xRRGGBBAA
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let c = Color::new(1.0, 0.0, 0.0, 0.5);
+    /// assert_eq!(c.to_u32_rgba(), 0xff00007f);
+    /// ```
+    pub fn to_u32_rgba(&self) -> u32 {
+        u32::from_be_bytes(self.to_rgba8())
+    }
+    /// Convert Color into a packed 32-bit ARGB value, 0xAARRGGBB
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let c = Color::new(1.0, 0.0, 0.0, 0.5);
+    /// assert_eq!(c.to_u32_argb(), 0x7fff0000);
+    /// ```
+    pub fn to_u32_argb(&self) -> u32 {
+        let [r, g, b, a] = self.to_rgba8();
+        u32::from_be_bytes([a, r, g, b])
+    }
+    /// Create new Color from a 16-bit RGB565 value
+    ///   alpha value set to 1.0
+    ///
+    ///   Channels are expanded to 8 bits by bit replication, so
+    ///   full intensity maps back to 255
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// assert_eq!(Color::from_rgb565(0xf800).to_rgb255(), (255, 0, 0));
+    /// assert_eq!(Color::from_rgb565(0x07e0).to_rgb255(), (0, 255, 0));
+    /// ```
+    pub fn from_rgb565(v: u16) -> Color {
+        Color::from_rgb255(expand((v >> 11) as u8, 5),
+                           expand((v >> 5) as u8 & 0x3f, 6),
+                           expand(v as u8 & 0x1f, 5))
+    }
+    /// Convert Color into a 16-bit RGB565 value
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let c = Color::from_rgb255(255, 128, 0);
+    /// assert_eq!(c.to_rgb565(), 0xfc00);
+    /// ```
+    pub fn to_rgb565(&self) -> u16 {
+        let (r, g, b) = self.to_rgb255();
+        (r as u16 >> 3) << 11 | (g as u16 >> 2) << 5 | b as u16 >> 3
+    }
+    /// Create new Color from a 15-bit RGB555 value, the top bit is ignored
+    ///   alpha value set to 1.0
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// assert_eq!(Color::from_rgb555(0x7c00).to_rgb255(), (255, 0, 0));
+    /// assert_eq!(Color::from_rgb555(0x001f).to_rgb255(), (0, 0, 255));
+    /// ```
+    pub fn from_rgb555(v: u16) -> Color {
+        Color::from_rgb255(expand((v >> 10) as u8 & 0x1f, 5),
+                           expand((v >> 5) as u8 & 0x1f, 5),
+                           expand(v as u8 & 0x1f, 5))
+    }
+    /// Convert Color into a 15-bit RGB555 value
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let c = Color::from_rgb255(255, 128, 0);
+    /// assert_eq!(c.to_rgb555(), 0x7e00);
+    /// ```
+    pub fn to_rgb555(&self) -> u16 {
+        let (r, g, b) = self.to_rgb255();
+        (r as u16 >> 3) << 10 | (g as u16 >> 3) << 5 | b as u16 >> 3
+    }
+    /// Create new Color from an 8-bit RGB332 value
+    ///   alpha value set to 1.0
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// assert_eq!(Color::from_rgb332(0xe0).to_rgb255(), (255, 0, 0));
+    /// assert_eq!(Color::from_rgb332(0x03).to_rgb255(), (0, 0, 255));
+    /// ```
+    pub fn from_rgb332(v: u8) -> Color {
+        Color::from_rgb255(expand(v >> 5, 3),
+                           expand(v >> 2 & 0x07, 3),
+                           expand(v & 0x03, 2))
+    }
+    /// Convert Color into an 8-bit RGB332 value
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let c = Color::from_rgb255(255, 128, 0);
+    /// assert_eq!(c.to_rgb332(), 0xf0);
+    /// ```
+    pub fn to_rgb332(&self) -> u8 {
+        let (r, g, b) = self.to_rgb255();
+        (r >> 5) << 5 | (g >> 5) << 2 | b >> 6
+    }
+}
+
+/// Expand an n-bit value to 8 bits by replicating its high bits
+fn expand(v: u8, bits: u32) -> u8 {
+    let mut out = 0u32;
+    let mut shift = 8i32 - bits as i32;
+    while shift > -(bits as i32) {
+        out |= if shift >= 0 { (v as u32) << shift } else { (v as u32) >> -shift };
+        shift -= bits as i32;
+    }
+    out as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn u32() {
+        for &v in [0x00000000, 0xffffffff, 0x12345678, 0xc0ffee80].iter() {
+            assert_eq!(Color::from_u32_rgba(v).to_u32_rgba(), v);
+            assert_eq!(Color::from_u32_argb(v).to_u32_argb(), v);
+        }
+        assert_eq!(Color::from_u32_rgba(0x12345678).to_u32_argb(), 0x78123456);
+    }
+    #[test]
+    fn expansion() {
+        assert_eq!(expand(0x1f, 5), 0xff);
+        assert_eq!(expand(0x10, 5), 0x84);
+        assert_eq!(expand(0x3f, 6), 0xff);
+        assert_eq!(expand(0x03, 2), 0xff);
+        assert_eq!(expand(0x01, 2), 0x55);
+        assert_eq!(expand(0x05, 3), 0xb6);
+        assert_eq!(expand(0, 5), 0);
+    }
+    #[test]
+    fn packed16() {
+        for v in 0..=0xffff_u16 {
+            assert_eq!(Color::from_rgb565(v).to_rgb565(), v);
+            assert_eq!(Color::from_rgb555(v).to_rgb555(), v & 0x7fff);
+        }
+        for v in 0..=0xff_u8 {
+            assert_eq!(Color::from_rgb332(v).to_rgb332(), v);
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//! Strict and lenient color parsing
+
+use Color;
+use ParseColorError;
+use CSS_COLORS;
+
+impl Color {
+    /// Parse a named color, a hex string or a CSS function
+    ///
+    ///   Names are looked up in the named color database first.
+    ///   Strings containing parentheses are parsed as CSS functions,
+    ///   strings starting with '#' or made up only of hex digits are
+    ///   parsed as hex.  The error returned describes the stage
+    ///   that failed:
+    ///      UnknownName - not a known name, hex or function
+    ///      InvalidHexLength, InvalidHexDigit - hex parsing
+    ///      InvalidSyntax, InvalidFunction, InvalidArgumentCount,
+    ///      InvalidNumber - functional parsing
+    ///
+    /// ```
+    /// # use tint::{Color, ParseColorError};
+    /// let red = Color::from_rgb255(255, 0, 0);
+    /// assert_eq!(Color::parse("red"), Ok(red));
+    /// assert_eq!(Color::parse("#ff0000"), Ok(red));
+    /// assert_eq!(Color::parse("ff0000"), Ok(red));
+    /// assert_eq!(Color::parse("rgb(255 0 0)"), Ok(red));
+    ///
+    /// assert_eq!(Color::parse("redd"), Err(ParseColorError::UnknownName("redd".to_string())));
+    /// assert_eq!(Color::parse("#ff00001"), Err(ParseColorError::InvalidHexLength(7)));
+    /// assert_eq!(Color::parse("rgb(255 0)"), Err(ParseColorError::InvalidArgumentCount(2)));
+    /// ```
+    pub fn parse(s: &str) -> Result<Color, ParseColorError> {
+        if s.is_empty() {
+            return Err(ParseColorError::Empty);
+        }
+        if let Some(c) = Color::name(s) {
+            return Ok(c);
+        }
+        if s.contains('(') || s.contains(')') {
+            return Color::from_css(s);
+        }
+        if s.starts_with('#') || s.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Color::try_from_hex(s);
+        }
+        Err(ParseColorError::UnknownName(s.to_owned()))
+    }
+    /// Parse a color following the CSS specification
+    ///
+    ///   Accepts CSS named colors and "transparent" (ignoring case),
+    ///   hex colors with a leading '#', and functional notation
+    ///   such as rgb() and hsl().  Surrounding whitespace, hex without
+    ///   a '#' and names outside of CSS, e.g. XKCD colors, are rejected.
+    ///
+    /// ```
+    /// # use tint::{Color, ParseColorError};
+    /// let red = Color::from_rgb255(255, 0, 0);
+    /// assert_eq!(Color::parse_strict("#ff0000"), Ok(red));
+    /// assert_eq!(Color::parse_strict("Red"), Ok(red));
+    /// assert_eq!(Color::parse_strict("rgb(255 0 0)"), Ok(red));
+    /// assert_eq!(Color::parse_strict("transparent"), Ok(Color::new(0.0, 0.0, 0.0, 0.0)));
+    ///
+    /// assert_eq!(Color::parse_strict("ff0000"),
+    ///            Err(ParseColorError::UnknownName("ff0000".to_string())));
+    /// assert_eq!(Color::parse_strict(" red"), Err(ParseColorError::InvalidSyntax));
+    /// ```
+    pub fn parse_strict(s: &str) -> Result<Color, ParseColorError> {
+        if s.is_empty() {
+            return Err(ParseColorError::Empty);
+        }
+        if s.trim() != s {
+            return Err(ParseColorError::InvalidSyntax);
+        }
+        if s.starts_with('#') {
+            return Color::try_from_hex(s);
+        }
+        if s.contains('(') {
+            return Color::from_css(s);
+        }
+        let name = s.to_ascii_lowercase();
+        if name == "transparent" {
+            return Ok(Color::new(0.0, 0.0, 0.0, 0.0));
+        }
+        match CSS_COLORS.get(&name) {
+            Some(&c) => Ok(c),
+            None => Err(ParseColorError::UnknownName(s.to_owned())),
+        }
+    }
+    /// Parse a color, accepting common variations
+    ///
+    ///   Surrounding whitespace is ignored and case does not matter.
+    ///   Accepts any name in the named color database, including XKCD
+    ///   colors if loaded, hex colors with or without a leading '#',
+    ///   and functional notation such as rgb() and hsl()
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let red = Color::from_rgb255(255, 0, 0);
+    /// assert_eq!(Color::parse_lenient("  FF0000 "), Ok(red));
+    /// assert_eq!(Color::parse_lenient("#F00"), Ok(red));
+    /// assert_eq!(Color::parse_lenient("RED"), Ok(red));
+    /// assert_eq!(Color::parse_lenient("RGB(255, 0, 0)"), Ok(red));
+    /// tint::xkcd();
+    /// assert_eq!(Color::parse_lenient(" Toxic Green").unwrap().to_hex(), "#61de2a");
+    /// ```
+    pub fn parse_lenient(s: &str) -> Result<Color, ParseColorError> {
+        let s = s.trim().to_lowercase();
+        if s.is_empty() {
+            return Err(ParseColorError::Empty);
+        }
+        if s == "transparent" {
+            return Ok(Color::new(0.0, 0.0, 0.0, 0.0));
+        }
+        Color::parse(&s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn front_door() {
+        assert_eq!(Color::parse(""), Err(ParseColorError::Empty));
+        assert_eq!(Color::parse("fff"), Ok(Color::from_rgb1(1.0, 1.0, 1.0)));
+        assert_eq!(Color::parse("#ggg"), Err(ParseColorError::InvalidHexDigit('g')));
+        assert_eq!(Color::parse("rgb(1 2 3"), Err(ParseColorError::InvalidSyntax));
+        assert_eq!(Color::parse("foo(1 2 3)"), Err(ParseColorError::InvalidFunction("foo".to_string())));
+        assert_eq!(Color::parse("not a color"),
+                   Err(ParseColorError::UnknownName("not a color".to_string())));
+    }
+    #[test]
+    fn strict() {
+        assert!(Color::parse_strict("#fff").is_ok());
+        assert!(Color::parse_strict("#ffff").is_ok());
+        assert!(Color::parse_strict("#ffffff").is_ok());
+        assert!(Color::parse_strict("#ffffffff").is_ok());
+        assert!(Color::parse_strict("WHITE").is_ok());
+        assert!(Color::parse_strict("hsl(0 0% 100%)").is_ok());
+        assert_eq!(Color::parse_strict(""), Err(ParseColorError::Empty));
+        assert_eq!(Color::parse_strict("#ff00"), Ok(Color::new(1.0, 1.0, 0.0, 0.0)));
+        assert_eq!(Color::parse_strict("#ff000"), Err(ParseColorError::InvalidHexLength(5)));
+        assert_eq!(Color::parse_strict("#fff "), Err(ParseColorError::InvalidSyntax));
+        assert_eq!(Color::parse_strict("toxic green"),
+                   Err(ParseColorError::UnknownName("toxic green".to_string())));
+    }
+    #[test]
+    fn lenient() {
+        let white = Color::from_rgb255(255, 255, 255);
+        assert_eq!(Color::parse_lenient("fff"), Ok(white));
+        assert_eq!(Color::parse_lenient("\t#FFFFFF\n"), Ok(white));
+        assert_eq!(Color::parse_lenient(" White "), Ok(white));
+        assert_eq!(Color::parse_lenient(" hsl( 0, 0%, 100% ) "), Ok(white));
+        assert_eq!(Color::parse_lenient("  "), Err(ParseColorError::Empty));
+        assert_eq!(Color::parse_lenient("#ggg"), Err(ParseColorError::InvalidHexDigit('g')));
+    }
+}
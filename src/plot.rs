@@ -0,0 +1,41 @@
+//! Conversions into plotters colors, with the "plotters" feature
+
+use plotters::style::{RGBAColor, RGBColor};
+use Color;
+
+impl From<Color> for RGBColor {
+    /// Opaque plotters color, alpha is ignored
+    ///
+    /// ```
+    /// # extern crate plotters;
+    /// # extern crate tint;
+    /// # use plotters::style::RGBColor;
+    /// # fn main() {
+    /// let viridis = tint::colormap("viridis").unwrap();
+    /// let color = RGBColor::from(viridis.sample(0.0));
+    /// assert_eq!(color, RGBColor(0x44, 0x01, 0x54));
+    /// # }
+    /// ```
+    fn from(c: Color) -> RGBColor {
+        let (r, g, b) = c.to_rgb255();
+        RGBColor(r, g, b)
+    }
+}
+
+impl From<Color> for RGBAColor {
+    fn from(c: Color) -> RGBAColor {
+        let (r, g, b) = c.to_rgb255();
+        RGBAColor(r, g, b, c.alpha)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn plotters() {
+        let c = Color::new(1.0, 0.5, 0.0, 0.25);
+        assert_eq!(RGBColor::from(c), RGBColor(255, 127, 0));
+        assert_eq!(RGBAColor::from(c), RGBAColor(255, 127, 0, 0.25));
+    }
+}
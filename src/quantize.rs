@@ -0,0 +1,462 @@
+//! Color quantization, reducing pixels to a small weighted palette
+//!
+//! Each quantizer takes a slice of pixel Colors and the number of
+//! palette entries, and returns up to that many Colors, each with the
+//! fraction of pixels it represents, sorted by decreasing weight.
+//! Pixels are grouped by their 8-bit RGB values first and alpha is
+//! ignored; palette Colors are opaque and truncated to 8-bit values,
+//! as in Color::to_rgb255().
+//!
+//! ```
+//! use tint::Color;
+//! let mut pixels = vec![Color::from("navy"); 300];
+//! pixels.extend(vec![Color::from("orange"); 100]);
+//! let palette = tint::quantize::kmeans(&pixels, 2);
+//! assert_eq!((palette[0].0.nearest_name().0.as_str(), palette[0].1), ("navy", 0.75));
+//! assert_eq!((palette[1].0.nearest_name().0.as_str(), palette[1].1), ("orange", 0.25));
+//! ```
+
+use std::collections::HashMap;
+
+use adjust::clip;
+use batch::blocks;
+use Color;
+
+/// Maximum number of k-means iterations
+const MAX_ITER: usize = 64;
+
+/// Cluster pixels with k-means in Oklab
+///
+///   Centers are seeded with k-means++ from a fixed seed, so results
+///   are deterministic.  With the "rayon" feature, pixels are assigned
+///   to clusters in parallel
+pub fn kmeans(pixels: &[Color], k: usize) -> Vec<(Color, f64)> {
+    let hist = histogram(pixels);
+    if k == 0 {
+        return vec![];
+    }
+    let points: Vec<([f64;3], f64)> = hist.iter()
+        .map(|&(c, w)| (oklab(&c), w))
+        .collect();
+    let mut centers = seed(&points, k);
+    let mut assign = vec![0; points.len()];
+    for iter in 0..MAX_ITER {
+        let mut next = vec![0; points.len()];
+        blocks(&points, 1, &mut next, 1, |s, o| {
+            for (p, a) in s.iter().zip(o.iter_mut()) {
+                *a = nearest(&centers, &p.0).0;
+            }
+        });
+        if iter > 0 && next == assign {
+            break;
+        }
+        assign = next;
+        let sums = sums(&points, &assign, centers.len());
+        for (c, s) in centers.iter_mut().zip(sums.iter()) {
+            if s[3] > 0.0 {
+                *c = [s[0] / s[3], s[1] / s[3], s[2] / s[3]];
+            }
+        }
+        // Move empty clusters to the point farthest from its center
+        for j in 0..centers.len() {
+            if sums[j][3] == 0.0 {
+                let far = points.iter().zip(assign.iter())
+                    .map(|(p, &a)| p.1 * dist2(&p.0, &centers[a]))
+                    .enumerate()
+                    .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+                    .map(|(i, _)| i);
+                if let Some(i) = far {
+                    centers[j] = points[i].0;
+                    assign[i] = j;
+                }
+            }
+        }
+    }
+    let palette = sums(&points, &assign, centers.len()).into_iter()
+        .filter(|s| s[3] > 0.0)
+        .map(|s| (clip(Color::from_oklab(s[0] / s[3], s[1] / s[3], s[2] / s[3]), 1.0), s[3]))
+        .collect();
+    finish(palette, pixels.len())
+}
+
+/// Quantize pixels by median cut in RGB
+///
+///   Starting from one box holding every color, the box with the widest
+///   range on any axis is split at the weighted median of that axis
+///   until there are n boxes.  Each palette Color is the mean of its
+///   box.  Faster than kmeans() and deterministic, though clusters
+///   follow the RGB axes rather than perceived differences
+///
+/// ```
+/// use tint::Color;
+/// let pixels: Vec<_> = (0..=255).map(|v| Color::from_rgb255(v, 0, 0)).collect();
+/// let palette = tint::quantize::median_cut(&pixels, 4);
+/// assert_eq!(palette.len(), 4);
+/// assert!(palette.iter().all(|p| p.1 == 0.25));
+/// ```
+pub fn median_cut(pixels: &[Color], n: usize) -> Vec<(Color, f64)> {
+    let mut points: Vec<([f64;3], f64)> = histogram(pixels).iter()
+        .map(|&(c, w)| ([c.red, c.green, c.blue], w))
+        .collect();
+    if n == 0 || points.is_empty() {
+        return vec![];
+    }
+    let mut boxes = vec![(0, points.len())];
+    while boxes.len() < n {
+        let widest = boxes.iter().enumerate()
+            .filter(|(_, b)| b.1 - b.0 > 1)
+            .map(|(i, b)| {
+                let (axis, range) = widest_axis(&points[b.0..b.1]);
+                (i, axis, range)
+            })
+            .fold(None, |best: Option<(usize, usize, f64)>, w| match best {
+                Some(b) if b.2 >= w.2 => Some(b),
+                _ => Some(w),
+            });
+        let (i, axis, _) = match widest {
+            Some(w) => w,
+            None => break,
+        };
+        let (start, end) = boxes[i];
+        let pts = &mut points[start..end];
+        pts.sort_by(|a, b| a.0[axis].partial_cmp(&b.0[axis]).unwrap());
+        let half = pts.iter().map(|p| p.1).sum::<f64>() / 2.0;
+        let mut acc = 0.0;
+        let mid = pts.iter()
+            .position(|p| { acc += p.1; acc >= half })
+            .map_or(1, |j| (j + 1).clamp(1, pts.len() - 1));
+        boxes[i] = (start, start + mid);
+        boxes.push((start + mid, end));
+    }
+    let palette = boxes.iter().map(|&(start, end)| {
+        let pts = &points[start..end];
+        let w: f64 = pts.iter().map(|p| p.1).sum();
+        let m = |i: usize| pts.iter().map(|p| p.0[i] * p.1).sum::<f64>() / w;
+        (Color::from_rgb1(m(0), m(1), m(2)), w)
+    }).collect();
+    finish(palette, pixels.len())
+}
+
+/// Axis with the largest range of values and that range
+fn widest_axis(points: &[([f64;3], f64)]) -> (usize, f64) {
+    (0..3).map(|axis| {
+        let (lo, hi) = points.iter().fold((f64::INFINITY, f64::NEG_INFINITY),
+                                          |(lo, hi), p| (lo.min(p.0[axis]), hi.max(p.0[axis])));
+        (axis, hi - lo)
+    }).fold((0, f64::NEG_INFINITY), |best, a| if a.1 > best.1 { a } else { best })
+}
+
+/// Quantize pixels with an octree, see Octree
+///
+/// ```
+/// use tint::Color;
+/// let pixels = [Color::from("red"), Color::from("red"), Color::from("blue")];
+/// let palette = tint::quantize::octree(&pixels, 2);
+/// assert_eq!(palette[0], (Color::from("red"), 2.0 / 3.0));
+/// assert_eq!(palette[1], (Color::from("blue"), 1.0 / 3.0));
+/// ```
+pub fn octree(pixels: &[Color], n: usize) -> Vec<(Color, f64)> {
+    let mut tree = Octree::new(n);
+    for c in pixels {
+        tree.add(c);
+    }
+    tree.palette(n)
+}
+
+/// Octree color quantizer for streaming pixels
+///
+///   Pixels are added one at a time into a tree indexed by the bits of
+///   their 8-bit RGB values.  When the number of leaves exceeds the
+///   limit, the deepest, most recently created branch is merged into a
+///   single leaf, so memory stays bounded however many pixels are
+///   added.  Each palette Color is the mean of the pixels in a leaf
+///
+/// ```
+/// use tint::Color;
+/// use tint::quantize::Octree;
+/// let mut tree = Octree::new(16);
+/// for v in 0..=255 {
+///     tree.add(&Color::from_rgb255(v, v, v));
+/// }
+/// assert!(tree.len() <= 16);
+/// let palette = tree.palette(4);
+/// assert_eq!(palette.len(), 4);
+/// ```
+#[derive(Debug,Clone)]
+pub struct Octree {
+    nodes: Vec<Node>,
+    /// Internal nodes by depth, in order of creation
+    reducible: Vec<Vec<usize>>,
+    /// Indices of removed nodes for reuse
+    free: Vec<usize>,
+    leaves: usize,
+    max_leaves: usize,
+    count: usize,
+}
+
+#[derive(Debug,Clone,Default)]
+struct Node {
+    children: [usize; 8],
+    sum: [f64; 3],
+    count: usize,
+    leaf: bool,
+}
+
+/// Depth of leaves holding a single 8-bit color
+const DEPTH: usize = 8;
+
+impl Octree {
+    /// Create an empty tree holding at most max_leaves colors, at least 1
+    pub fn new(max_leaves: usize) -> Octree {
+        let mut reducible = vec![vec![]; DEPTH];
+        reducible[0].push(0);
+        Octree {
+            nodes: vec![Node::default()],
+            reducible,
+            free: vec![],
+            leaves: 0,
+            max_leaves: max_leaves.max(1),
+            count: 0,
+        }
+    }
+    /// Add a pixel, merging leaves if over the limit
+    pub fn add(&mut self, c: &Color) {
+        let (r, g, b) = c.to_rgb255();
+        let mut node = 0;
+        for depth in 0..DEPTH {
+            if self.nodes[node].leaf {
+                break;
+            }
+            let shift = 7 - depth;
+            let i = (((r >> shift) & 1) << 2 | ((g >> shift) & 1) << 1 | ((b >> shift) & 1)) as usize;
+            if self.nodes[node].children[i] == 0 {
+                let child = self.alloc(depth + 1);
+                self.nodes[node].children[i] = child;
+            }
+            node = self.nodes[node].children[i];
+        }
+        let n = &mut self.nodes[node];
+        n.sum[0] += c.red;
+        n.sum[1] += c.green;
+        n.sum[2] += c.blue;
+        n.count += 1;
+        self.count += 1;
+        while self.leaves > self.max_leaves && self.reduce() {}
+    }
+    /// Number of colors currently held
+    pub fn len(&self) -> usize {
+        self.leaves
+    }
+    /// True if no pixels have been added
+    pub fn is_empty(&self) -> bool {
+        self.leaves == 0
+    }
+    /// Palette of at most n colors, with the fraction of pixels each represents
+    pub fn palette(&self, n: usize) -> Vec<(Color, f64)> {
+        if n == 0 || self.count == 0 {
+            return vec![];
+        }
+        let mut tree = self.clone();
+        while tree.leaves > n && tree.reduce() {}
+        let palette = tree.nodes.iter()
+            .filter(|node| node.leaf && node.count > 0)
+            .map(|node| {
+                let w = node.count as f64;
+                (Color::from_rgb1(node.sum[0] / w, node.sum[1] / w, node.sum[2] / w), w)
+            })
+            .collect();
+        finish(palette, self.count)
+    }
+    /// New empty node at depth, a leaf at the deepest level
+    fn alloc(&mut self, depth: usize) -> usize {
+        let node = Node { leaf: depth == DEPTH, ..Node::default() };
+        let i = match self.free.pop() {
+            Some(i) => { self.nodes[i] = node; i }
+            None => { self.nodes.push(node); self.nodes.len() - 1 }
+        };
+        if depth == DEPTH {
+            self.leaves += 1;
+        } else {
+            self.reducible[depth].push(i);
+        }
+        i
+    }
+    /// Merge the children of the deepest, newest internal node into it
+    ///   Returns false if there is nothing to merge
+    fn reduce(&mut self) -> bool {
+        let node = match self.reducible.iter_mut().rev().find_map(|level| level.pop()) {
+            Some(node) => node,
+            None => return false,
+        };
+        let children = self.nodes[node].children;
+        let mut merged = 0;
+        for &child in children.iter().filter(|&&c| c != 0) {
+            let Node { sum, count, .. } = self.nodes[child];
+            let n = &mut self.nodes[node];
+            for (s, v) in n.sum.iter_mut().zip(sum.iter()) {
+                *s += v;
+            }
+            n.count += count;
+            self.nodes[child] = Node::default();
+            self.free.push(child);
+            merged += 1;
+        }
+        let n = &mut self.nodes[node];
+        n.children = [0; 8];
+        n.leaf = true;
+        self.leaves = self.leaves + 1 - merged;
+        true
+    }
+}
+
+/// Distinct 8-bit colors and their pixel counts
+fn histogram(pixels: &[Color]) -> Vec<(Color, f64)> {
+    let mut counts: HashMap<(u8,u8,u8), usize> = HashMap::new();
+    for c in pixels {
+        *counts.entry(c.to_rgb255()).or_insert(0) += 1;
+    }
+    let mut hist: Vec<_> = counts.into_iter()
+        .map(|((r, g, b), n)| (Color::from_rgb255(r, g, b), n as f64))
+        .collect();
+    hist.sort_by_key(|h| h.0.to_rgb255());
+    hist
+}
+
+/// Truncate colors to 8-bit values as in Color::to_rgb255(), normalize
+///   weights by the number of pixels and sort by decreasing weight
+fn finish(mut palette: Vec<(Color, f64)>, n: usize) -> Vec<(Color, f64)> {
+    for p in palette.iter_mut() {
+        let (r, g, b) = p.0.to_rgb255();
+        p.0 = Color::from_rgb255(r, g, b);
+        p.1 /= n as f64;
+    }
+    palette.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+    palette
+}
+
+fn oklab(c: &Color) -> [f64;3] {
+    let (l, a, b) = c.to_oklab();
+    [l, a, b]
+}
+
+fn dist2(a: &[f64;3], b: &[f64;3]) -> f64 {
+    (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)
+}
+
+/// Index of and squared distance to the nearest center
+fn nearest(centers: &[[f64;3]], p: &[f64;3]) -> (usize, f64) {
+    centers.iter().map(|c| dist2(c, p)).enumerate()
+        .fold((0, f64::INFINITY), |best, (i, d)| if d < best.1 { (i, d) } else { best })
+}
+
+/// Weighted sums of components and weights in each cluster
+fn sums(points: &[([f64;3], f64)], assign: &[usize], k: usize) -> Vec<[f64;4]> {
+    let mut sums = vec![[0.0; 4]; k];
+    for (p, &a) in points.iter().zip(assign.iter()) {
+        let s = &mut sums[a];
+        for (s, v) in s.iter_mut().zip(p.0.iter()) {
+            *s += v * p.1;
+        }
+        s[3] += p.1;
+    }
+    sums
+}
+
+/// k-means++ seeding, at most one center per point
+fn seed(points: &[([f64;3], f64)], k: usize) -> Vec<[f64;3]> {
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+    let mut centers = vec![];
+    let mut d2: Vec<f64> = points.iter().map(|p| p.1).collect();
+    while centers.len() < k {
+        let total: f64 = d2.iter().sum();
+        if total <= 0.0 {
+            break;
+        }
+        let mut r = rng.next() * total;
+        let mut pick = 0;
+        for (i, d) in d2.iter().enumerate() {
+            pick = i;
+            r -= d;
+            if r < 0.0 && *d > 0.0 {
+                break;
+            }
+        }
+        let c = points[pick].0;
+        centers.push(c);
+        for (d, p) in d2.iter_mut().zip(points.iter()) {
+            *d = d.min(p.1 * dist2(&p.0, &c));
+        }
+    }
+    centers
+}
+
+/// Small deterministic generator for seeding
+struct XorShift(u64);
+
+impl XorShift {
+    /// Uniform value in [0,1)
+    fn next(&mut self) -> f64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn kmeans_clusters() {
+        let mut pixels = vec![];
+        for i in 0..50 {
+            pixels.push(Color::from_rgb255(200 + i % 5, 10, 10));
+            pixels.push(Color::from_rgb255(10, 10, 200 + i % 5));
+            pixels.push(Color::from_rgb255(10, 200 + i % 5, 10));
+            pixels.push(Color::from_rgb255(10, 200 + i % 5, 10));
+        }
+        let palette = kmeans(&pixels, 3);
+        assert_eq!(palette.len(), 3);
+        assert_eq!(palette.iter().map(|p| p.1).collect::<Vec<_>>(), vec![0.5, 0.25, 0.25]);
+        let (r, g, b) = palette[0].0.to_rgb255();
+        assert!(g > 190 && r < 20 && b < 20);
+        assert_eq!(kmeans(&pixels, 3), palette);
+
+        let palette = kmeans(&pixels, 100);
+        assert_eq!(palette.len(), 15);
+        assert!((palette.iter().map(|p| p.1).sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!(kmeans(&pixels, 0).is_empty());
+        assert!(kmeans(&[], 4).is_empty());
+    }
+    #[test]
+    fn median_cut_boxes() {
+        let mut pixels = vec![];
+        for i in 0..10 {
+            pixels.push(Color::from_rgb255(250 - 2 * i, 0, 0));
+            pixels.push(Color::from_rgb255(0, 0, 250 - 2 * i));
+        }
+        let palette = median_cut(&pixels, 2);
+        assert_eq!(palette, vec![(Color::from_rgb255(0, 0, 241), 0.5),
+                                 (Color::from_rgb255(241, 0, 0), 0.5)]);
+        assert_eq!(median_cut(&pixels, 2), palette);
+        assert_eq!(median_cut(&pixels, 50).len(), 20);
+        assert!(median_cut(&pixels, 0).is_empty());
+        assert!(median_cut(&[], 4).is_empty());
+    }
+    #[test]
+    fn octree_limit() {
+        let pixels: Vec<_> = (0..4096).map(|i| Color::from_rgb255((i * 37) as u8, (i * 11) as u8, (i / 16) as u8)).collect();
+        let mut tree = Octree::new(64);
+        for c in &pixels {
+            tree.add(c);
+            assert!(tree.len() <= 64);
+        }
+        assert!(tree.nodes.len() < 64 * 8 * 2);
+        let palette = tree.palette(8);
+        assert!(!palette.is_empty() && palette.len() <= 8);
+        assert!((palette.iter().map(|p| p.1).sum::<f64>() - 1.0).abs() < 1e-12);
+        assert_eq!(octree(&pixels[..3], 8).len(), 3);
+        assert!(octree(&pixels, 0).is_empty());
+        assert!(Octree::new(4).palette(4).is_empty());
+    }
+}
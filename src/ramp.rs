@@ -0,0 +1,137 @@
+//! Color ramps generated from a single color
+
+use Color;
+
+/// Scale steps with Oklch lightness and relative chroma targets
+static SCALE: [(u16, f64, f64); 11] = [
+    (50,  0.971, 0.10),
+    (100, 0.936, 0.25),
+    (200, 0.885, 0.45),
+    (300, 0.808, 0.70),
+    (400, 0.704, 0.90),
+    (500, 0.637, 1.00),
+    (600, 0.577, 1.00),
+    (700, 0.505, 0.90),
+    (800, 0.444, 0.80),
+    (900, 0.396, 0.70),
+    (950, 0.258, 0.50),
+];
+
+impl Color {
+    /// n colors mixed evenly from the Color to black, inclusive
+    ///
+    ///   Alpha is unchanged
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let red = Color::from_rgb255(255, 0, 0);
+    /// let hex : Vec<_> = red.shades(3).iter().map(|c| c.to_hex()).collect();
+    /// assert_eq!(hex, vec!["#ff0000", "#7f0000", "#000000"]);
+    /// ```
+    pub fn shades(&self, n: usize) -> Vec<Color> {
+        self.ramp(0.0, n)
+    }
+    /// n colors mixed evenly from the Color to white, inclusive
+    ///
+    ///   Alpha is unchanged
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let red = Color::from_rgb255(255, 0, 0);
+    /// let hex : Vec<_> = red.tints(3).iter().map(|c| c.to_hex()).collect();
+    /// assert_eq!(hex, vec!["#ff0000", "#ff7f7f", "#ffffff"]);
+    /// ```
+    pub fn tints(&self, n: usize) -> Vec<Color> {
+        self.ramp(1.0, n)
+    }
+    /// n colors mixed evenly from the Color to middle gray, inclusive
+    ///
+    ///   Alpha is unchanged
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let red = Color::from_rgb255(255, 0, 0);
+    /// let hex : Vec<_> = red.tones(3).iter().map(|c| c.to_hex()).collect();
+    /// assert_eq!(hex, vec!["#ff0000", "#bf3f3f", "#7f7f7f"]);
+    /// ```
+    pub fn tones(&self, n: usize) -> Vec<Color> {
+        self.ramp(0.5, n)
+    }
+    /// Tailwind style 11 step scale, labelled 50, 100 .. 900, 950
+    ///
+    ///   Steps keep the Oklch hue of the Color and follow fixed
+    ///   lightness targets, from near white at 50 to near black at 950.
+    ///   Chroma is tapered towards both ends and reduced where needed
+    ///   to stay inside the sRGB gamut.  Alpha is unchanged
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let scale = Color::from_rgb255(59, 130, 246).scale();
+    /// assert_eq!(scale.len(), 11);
+    /// assert_eq!(scale[0].0, 50);
+    /// assert_eq!(scale[10].0, 950);
+    /// assert!(scale[0].1.to_oklch().0 > scale[10].1.to_oklch().0);
+    /// ```
+    pub fn scale(&self) -> Vec<(u16, Color)> {
+        let (_,chroma,h) = self.to_oklch();
+        SCALE.iter().map(|&(step, l, k)| {
+            let c = chroma * k;
+            let mut f = 1.0;
+            if !in_gamut(&Color::from_oklch(l, c, h)) {
+                // Bisect on the fraction of chroma that stays in gamut
+                let (mut lo, mut hi) = (0.0, 1.0);
+                for _ in 0..32 {
+                    let mid = (lo + hi) / 2.0;
+                    if in_gamut(&Color::from_oklch(l, c * mid, h)) {
+                        lo = mid;
+                    } else {
+                        hi = mid;
+                    }
+                }
+                f = lo;
+            }
+            let c = Color::from_oklch(l, c * f, h);
+            (step, Color::new(c.red.clamp(0.0, 1.0), c.green.clamp(0.0, 1.0),
+                              c.blue.clamp(0.0, 1.0), self.alpha))
+        }).collect()
+    }
+    fn ramp(&self, gray: f64, n: usize) -> Vec<Color> {
+        let target = Color::new(gray, gray, gray, self.alpha);
+        (0..n).map(|i| {
+            let t = if n > 1 { i as f64 / (n - 1) as f64 } else { 0.0 };
+            self.lerp(&target, t)
+        }).collect()
+    }
+}
+
+fn in_gamut(c: &Color) -> bool {
+    [c.red, c.green, c.blue].iter().all(|&v| (-1e-9..=1.0 + 1e-9).contains(&v))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn ramps() {
+        let c = Color::new(0.2, 0.4, 0.6, 0.5);
+        assert!(c.shades(0).is_empty());
+        assert_eq!(c.tints(1), vec![c]);
+        let t = c.tones(5);
+        assert_eq!(t.len(), 5);
+        assert_eq!(t[0], c);
+        assert_eq!(t[4], Color::new(0.5, 0.5, 0.5, 0.5));
+        assert!(t.iter().all(|x| x.alpha == 0.5));
+    }
+    #[test]
+    fn scale() {
+        let s = Color::new(0.9, 0.1, 0.1, 0.5).scale();
+        let steps : Vec<_> = s.iter().map(|x| x.0).collect();
+        assert_eq!(steps, vec![50, 100, 200, 300, 400, 500, 600, 700, 800, 900, 950]);
+        for w in s.windows(2) {
+            assert!(w[0].1.to_oklch().0 > w[1].1.to_oklch().0);
+        }
+        assert!(s.iter().all(|x| x.1.alpha == 0.5));
+        let g = Color::new(0.5, 0.5, 0.5, 1.0).scale();
+        assert!(g.iter().all(|x| x.1.to_oklch().1 < 1e-6));
+    }
+}
@@ -0,0 +1,53 @@
+//! Random colors, enabled with the `rand` feature
+
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+use Color;
+
+/// Sample a Color with uniformly distributed red, green and blue
+///
+///   Alpha is set to 1.0
+impl Distribution<Color> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Color {
+        Color::from_rgb1(rng.gen(), rng.gen(), rng.gen())
+    }
+}
+
+impl Color {
+    /// Create a random Color using the supplied random number generator
+    ///   alpha value set to 1.0
+    ///
+    /// ```
+    /// # extern crate rand;
+    /// # extern crate tint;
+    /// # use tint::Color;
+    /// # fn main() {
+    /// let c = Color::random_with(&mut rand::thread_rng());
+    /// assert!(c.red >= 0.0 && c.red < 1.0);
+    /// assert_eq!(c.alpha, 1.0);
+    /// # }
+    /// ```
+    pub fn random_with<R: Rng + ?Sized>(rng: &mut R) -> Color {
+        rng.gen()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    #[test]
+    fn seeded() {
+        let mut a = StdRng::seed_from_u64(42);
+        let mut b = StdRng::seed_from_u64(42);
+        for _ in 0..100 {
+            let c = Color::random_with(&mut a);
+            assert_eq!(c, b.gen::<Color>());
+            for v in [c.red, c.green, c.blue].iter() {
+                assert!(*v >= 0.0 && *v < 1.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,71 @@
+//! Conversions to and from ratatui colors, with the "ratatui" feature
+
+use ratatui::style::Color as TuiColor;
+use Color;
+
+impl From<Color> for TuiColor {
+    fn from(c: Color) -> TuiColor {
+        let (r, g, b) = c.to_rgb255();
+        TuiColor::Rgb(r, g, b)
+    }
+}
+
+impl Color {
+    /// Closest xterm 256 indexed ratatui color, for terminals without truecolor
+    ///
+    /// ```
+    /// # extern crate ratatui;
+    /// # extern crate tint;
+    /// # use tint::Color;
+    /// # fn main() {
+    /// let red = Color::from("red");
+    /// assert_eq!(red.to_ratatui_indexed(), ratatui::style::Color::Indexed(196));
+    /// assert_eq!(ratatui::style::Color::from(red), ratatui::style::Color::Rgb(255, 0, 0));
+    /// # }
+    /// ```
+    pub fn to_ratatui_indexed(&self) -> TuiColor {
+        TuiColor::Indexed(self.to_ansi256())
+    }
+    /// Create new Color from a ratatui color
+    ///   alpha value set to 1.0
+    ///
+    ///   Named and indexed colors use the xterm defaults.  Reset, the
+    ///   terminal default color, returns None
+    pub fn from_ratatui(c: TuiColor) -> Option<Color> {
+        let index = match c {
+            TuiColor::Reset => return None,
+            TuiColor::Rgb(r, g, b) => return Some(Color::from_rgb255(r, g, b)),
+            TuiColor::Indexed(i) => i,
+            TuiColor::Black => 0,
+            TuiColor::Red => 1,
+            TuiColor::Green => 2,
+            TuiColor::Yellow => 3,
+            TuiColor::Blue => 4,
+            TuiColor::Magenta => 5,
+            TuiColor::Cyan => 6,
+            TuiColor::Gray => 7,
+            TuiColor::DarkGray => 8,
+            TuiColor::LightRed => 9,
+            TuiColor::LightGreen => 10,
+            TuiColor::LightYellow => 11,
+            TuiColor::LightBlue => 12,
+            TuiColor::LightMagenta => 13,
+            TuiColor::LightCyan => 14,
+            TuiColor::White => 15,
+        };
+        Some(Color::from_ansi256(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn ratatui() {
+        let c = Color::from_rgb255(10, 20, 30);
+        assert_eq!(Color::from_ratatui(TuiColor::from(c)), Some(c));
+        assert_eq!(Color::from_ratatui(TuiColor::Reset), None);
+        assert_eq!(Color::from_ratatui(TuiColor::LightRed), Some(Color::from("red")));
+        assert_eq!(Color::from_ratatui(TuiColor::Indexed(232)), Some(Color::from_ansi256(232)));
+    }
+}
@@ -0,0 +1,260 @@
+//! Serde support, enabled with the `serde` feature
+//!
+//! By default a Color serializes as a hex string, "#rrggbb", or
+//! "#rrggbbaa" if alpha is less than 1.0.  Deserialization accepts any
+//! representation: a string understood by `Color::parse`, a sequence of
+//! three or four numbers [0,1], or a map with `red`, `green`, `blue` and
+//! an optional `alpha`.  This needs a self-describing format like JSON.
+//!
+//! Other representations are selected per field with `#[serde(with = ...)]`.
+//! Each module deserializes only its own representation, so it also
+//! works with formats like bincode
+//!
+//! ```
+//! # #[macro_use] extern crate serde_derive;
+//! # extern crate serde_json;
+//! # extern crate tint;
+//! # use tint::Color;
+//! #[derive(Serialize, Deserialize)]
+//! struct Theme {
+//!     background: Color,                           // "#ffffff"
+//!     #[serde(with = "tint::serialize::name")]
+//!     foreground: Color,                           // "black"
+//!     #[serde(with = "tint::serialize::array")]
+//!     accent: Color,                               // [1.0, 0.5, 0.0, 1.0]
+//!     #[serde(with = "tint::serialize::map")]
+//!     border: Color,                               // {"red": 0.5, ...}
+//! }
+//! # fn main() {
+//! let theme = Theme {
+//!     background: Color::from("white"),
+//!     foreground: Color::from("black"),
+//!     accent: Color::from_rgb1(1.0, 0.5, 0.0),
+//!     border: Color::from_rgb1(0.5, 0.5, 0.5),
+//! };
+//! let json = serde_json::to_string(&theme).unwrap();
+//! assert_eq!(json, concat!(r##"{"background":"#ffffff","foreground":"black","##,
+//!                          r##""accent":[1.0,0.5,0.0,1.0],"##,
+//!                          r##""border":{"red":0.5,"green":0.5,"blue":0.5,"alpha":1.0}}"##));
+//! let back : Theme = serde_json::from_str(&json).unwrap();
+//! assert_eq!(back.accent, theme.accent);
+//! # }
+//! ```
+
+use std::fmt;
+
+use serde::de::{self, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{SerializeStruct, Serializer};
+use serde::{Deserialize, Serialize};
+
+use Color;
+use CSS_COLORS;
+use ParseColorError;
+
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        hex::serialize(self, serializer)
+    }
+}
+
+/// Accepts any representation, so needs a self-describing format such
+///   as JSON; use one of the modules below for other formats
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        deserializer.deserialize_any(ColorVisitor)
+    }
+}
+
+/// Hex string representation, "#rrggbb" or "#rrggbbaa"
+pub mod hex {
+    use super::*;
+    /// Serialize Color as a hex string
+    pub fn serialize<S: Serializer>(c: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+        if c.alpha < 1.0 {
+            serializer.serialize_str(&c.to_hex_rgba())
+        } else {
+            serializer.serialize_str(&c.to_hex())
+        }
+    }
+    /// Deserialize Color from a string understood by Color::parse
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        deserializer.deserialize_str(ColorVisitor)
+    }
+}
+
+/// Color name representation, falling back to a hex string
+///
+///   Only W3C names are written, so the output does not depend on
+///   whether other names, e.g. xkcd(), have been loaded
+pub mod name {
+    use super::*;
+    /// Serialize Color as a W3C name if an exact match exists, otherwise as hex
+    pub fn serialize<S: Serializer>(c: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+        match ::lookup_css_name(c) {
+            Some(name) if c.alpha >= 1.0 => serializer.serialize_str(&name),
+            _ => hex::serialize(c, serializer),
+        }
+    }
+    /// Deserialize Color from a W3C name or a hex string
+    ///
+    ///   As with serialize, names loaded later, e.g. xkcd(), are not accepted
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        deserializer.deserialize_str(NameVisitor)
+    }
+}
+
+/// Array representation, [red, green, blue, alpha]
+pub mod array {
+    use super::*;
+    /// Serialize Color as an array of red, green, blue and alpha
+    pub fn serialize<S: Serializer>(c: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq([c.red, c.green, c.blue, c.alpha].iter())
+    }
+    /// Deserialize Color from a sequence of 3 or 4 numbers
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        deserializer.deserialize_seq(ColorVisitor)
+    }
+}
+
+/// Map representation, {red, green, blue, alpha}
+pub mod map {
+    use super::*;
+    /// Serialize Color as a map of red, green, blue and alpha
+    pub fn serialize<S: Serializer>(c: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("Color", 4)?;
+        s.serialize_field("red", &c.red)?;
+        s.serialize_field("green", &c.green)?;
+        s.serialize_field("blue", &c.blue)?;
+        s.serialize_field("alpha", &c.alpha)?;
+        s.end()
+    }
+    /// Deserialize Color from a map of red, green, blue and optional alpha
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        deserializer.deserialize_struct("Color", FIELDS, ColorVisitor)
+    }
+}
+
+const FIELDS: &[&str] = &["red", "green", "blue", "alpha"];
+
+struct ColorVisitor;
+
+impl<'de> Visitor<'de> for ColorVisitor {
+    type Value = Color;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a color string, an array of 3 or 4 numbers, or a map of red, green, blue and alpha")
+    }
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Color, E> {
+        Color::parse(s).map_err(E::custom)
+    }
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Color, A::Error> {
+        let mut v = vec![];
+        while let Some(x) = seq.next_element::<f64>()? {
+            v.push(x);
+        }
+        match v.len() {
+            3 => Ok(Color::new(v[0], v[1], v[2], 1.0)),
+            4 => Ok(Color::new(v[0], v[1], v[2], v[3])),
+            n => Err(de::Error::invalid_length(n, &self)),
+        }
+    }
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Color, A::Error> {
+        let (mut r, mut g, mut b, mut a) = (None, None, None, None);
+        while let Some(key) = map.next_key::<String>()? {
+            let slot = match key.as_str() {
+                "red" | "r" => &mut r,
+                "green" | "g" => &mut g,
+                "blue" | "b" => &mut b,
+                "alpha" | "a" => &mut a,
+                _ => return Err(de::Error::unknown_field(&key, FIELDS)),
+            };
+            *slot = Some(map.next_value::<f64>()?);
+        }
+        let r = r.ok_or_else(|| de::Error::missing_field("red"))?;
+        let g = g.ok_or_else(|| de::Error::missing_field("green"))?;
+        let b = b.ok_or_else(|| de::Error::missing_field("blue"))?;
+        Ok(Color::new(r, g, b, a.unwrap_or(1.0)))
+    }
+}
+
+struct NameVisitor;
+
+impl<'de> Visitor<'de> for NameVisitor {
+    type Value = Color;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a W3C color name or a hex string")
+    }
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Color, E> {
+        if let Some(c) = CSS_COLORS.get(&s.to_lowercase()) {
+            return Ok(*c);
+        }
+        if s.starts_with('#') {
+            return Color::try_from_hex(s).map_err(E::custom);
+        }
+        Err(E::custom(ParseColorError::UnknownName(s.to_owned())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate bincode;
+    extern crate serde_json;
+    use super::*;
+    #[test]
+    fn representations() {
+        let c = Color::from_rgb255(255, 0, 0);
+        assert_eq!(serde_json::to_string(&c).unwrap(), "\"#ff0000\"");
+        let mut s = serde_json::Serializer::new(Vec::new());
+        name::serialize(&c, &mut s).unwrap();
+        assert_eq!(s.into_inner(), b"\"red\"");
+        let mut s = serde_json::Serializer::new(Vec::new());
+        array::serialize(&c, &mut s).unwrap();
+        assert_eq!(s.into_inner(), b"[1.0,0.0,0.0,1.0]");
+        let mut s = serde_json::Serializer::new(Vec::new());
+        map::serialize(&c, &mut s).unwrap();
+        assert_eq!(s.into_inner(), &b"{\"red\":1.0,\"green\":0.0,\"blue\":0.0,\"alpha\":1.0}"[..]);
+    }
+    #[test]
+    fn deserialize() {
+        let red = Color::from_rgb255(255, 0, 0);
+        for s in ["\"red\"", "\"#ff0000\"", "\"rgb(255 0 0)\"", "[1.0, 0.0, 0.0]",
+                  "[1, 0, 0, 1]", "{\"red\": 1, \"green\": 0, \"blue\": 0}"].iter() {
+            assert_eq!(serde_json::from_str::<Color>(s).unwrap(), red, "{}", s);
+        }
+        let c : Color = serde_json::from_str("\"#ff000080\"").unwrap();
+        assert_eq!(c.alpha, 128.0 / 255.0);
+        assert!(serde_json::from_str::<Color>("\"nope\"").is_err());
+        assert!(serde_json::from_str::<Color>("[1, 0]").is_err());
+        assert!(serde_json::from_str::<Color>("{\"red\": 1}").is_err());
+    }
+    #[test]
+    fn non_self_describing() {
+        let c = Color::new(0.25, 0.5, 0.75, 0.5);
+        macro_rules! round_trip {
+            ($m:ident) => {{
+                let mut buf = vec![];
+                $m::serialize(&c, &mut bincode::Serializer::new(&mut buf, bincode::DefaultOptions::new())).unwrap();
+                let mut de = bincode::Deserializer::from_slice(&buf, bincode::DefaultOptions::new());
+                $m::deserialize(&mut de).unwrap()
+            }};
+        }
+        assert_eq!(round_trip!(hex).to_hex_rgba(), c.to_hex_rgba());
+        assert_eq!(round_trip!(name).to_hex_rgba(), c.to_hex_rgba());
+        assert_eq!(round_trip!(array), c);
+        assert_eq!(round_trip!(map), c);
+    }
+    #[test]
+    fn name_ignores_loaded_names() {
+        ::xkcd();
+        let butterscotch = Color::name("butterscotch").unwrap();
+        let mut s = serde_json::Serializer::new(Vec::new());
+        name::serialize(&butterscotch, &mut s).unwrap();
+        assert_eq!(s.into_inner(), b"\"#fdb147\"");
+        let name = |s: &str| name::deserialize(&mut serde_json::Deserializer::from_str(s));
+        assert!(name("\"butterscotch\"").is_err());
+        assert!(name("\"rgb(255 0 0)\"").is_err());
+        assert_eq!(name("\"Red\"").unwrap(), Color::from_rgb255(255, 0, 0));
+        assert_eq!(name("\"#fdb147\"").unwrap(), butterscotch);
+    }
+}
@@ -0,0 +1,337 @@
+//! SIMD kernels for the batch conversions
+//!
+//! Each kernel converts as many whole groups of pixels as it can from
+//! the start of its buffers and returns the number of elements done,
+//! leaving the rest to the scalar loops in batch.  Those loops are also
+//! the reference the kernels are tested against, and results match them
+//! exactly.
+//!
+//! On x86_64 the kernels use AVX2, or SSSE3 for hex, when detected at
+//! runtime.  On aarch64 NEON is always present, but lacks the gathers
+//! needed for the lookup tables, so only HSV and hex are vectorized.
+//! Other targets use the scalar loops only.
+
+/// Convert u8 components to f32 through a 256 entry table
+#[cfg(target_arch = "x86_64")]
+pub(crate) fn srgb8_to_linear(src: &[u8], out: &mut [f32], table: &[f32;256]) -> usize {
+    if is_x86_feature_detected!("avx2") {
+        unsafe { x86::srgb8_to_linear(src, out, table) }
+    } else {
+        0
+    }
+}
+
+/// Convert f32 components to u8 through a bucket table of levels, all
+///   in [0, 255], and the smallest value of each level
+#[cfg(target_arch = "x86_64")]
+pub(crate) fn linear_to_srgb8(src: &[f32], out: &mut [u8], lut: &[i32], threshold: &[f32;257]) -> usize {
+    debug_assert!(lut.iter().all(|k| (0..=255).contains(k)));
+    if is_x86_feature_detected!("avx2") {
+        unsafe { x86::linear_to_srgb8(src, out, lut, threshold) }
+    } else {
+        0
+    }
+}
+
+/// Packed RGB to HSV, as batch::rgb_to_hsv
+#[cfg(target_arch = "x86_64")]
+pub(crate) fn rgb_to_hsv(src: &[f32], out: &mut [f32]) -> usize {
+    if is_x86_feature_detected!("avx2") {
+        unsafe { x86::rgb_to_hsv(src, out) }
+    } else {
+        0
+    }
+}
+
+/// Packed HSV to RGB, as batch::hsv_to_rgb
+#[cfg(target_arch = "x86_64")]
+pub(crate) fn hsv_to_rgb(src: &[f32], out: &mut [f32]) -> usize {
+    if is_x86_feature_detected!("avx2") {
+        unsafe { x86::hsv_to_rgb(src, out) }
+    } else {
+        0
+    }
+}
+
+/// Packed RGB pixels to "#rrggbb", returns the number of pixels done
+#[cfg(target_arch = "x86_64")]
+pub(crate) fn rgb8_to_hex(src: &[u8], out: &mut [u8]) -> usize {
+    if is_x86_feature_detected!("ssse3") {
+        unsafe { x86::rgb8_to_hex(src, out) }
+    } else {
+        0
+    }
+}
+
+// NEON is part of the aarch64 baseline, so needs no runtime check
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+pub(crate) fn rgb_to_hsv(src: &[f32], out: &mut [f32]) -> usize {
+    unsafe { neon::rgb_to_hsv(src, out) }
+}
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+pub(crate) fn hsv_to_rgb(src: &[f32], out: &mut [f32]) -> usize {
+    unsafe { neon::hsv_to_rgb(src, out) }
+}
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+pub(crate) fn rgb8_to_hex(src: &[u8], out: &mut [u8]) -> usize {
+    unsafe { neon::rgb8_to_hex(src, out) }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub(crate) fn srgb8_to_linear(_: &[u8], _: &mut [f32], _: &[f32;256]) -> usize {
+    0
+}
+#[cfg(not(target_arch = "x86_64"))]
+pub(crate) fn linear_to_srgb8(_: &[f32], _: &mut [u8], _: &[i32], _: &[f32;257]) -> usize {
+    0
+}
+#[cfg(not(any(target_arch = "x86_64", all(target_arch = "aarch64", target_feature = "neon"))))]
+pub(crate) fn rgb_to_hsv(_: &[f32], _: &mut [f32]) -> usize {
+    0
+}
+#[cfg(not(any(target_arch = "x86_64", all(target_arch = "aarch64", target_feature = "neon"))))]
+pub(crate) fn hsv_to_rgb(_: &[f32], _: &mut [f32]) -> usize {
+    0
+}
+#[cfg(not(any(target_arch = "x86_64", all(target_arch = "aarch64", target_feature = "neon"))))]
+pub(crate) fn rgb8_to_hex(_: &[u8], _: &mut [u8]) -> usize {
+    0
+}
+
+/// Hex digits as table indices, 4 pixels at a time: the first and second
+///   16 output bytes index 24 digits, two per input byte.  Out of range
+///   indices give zero for the '#' bytes, which are or'ed in with HASH
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+const HEX_LO: [u8;16] = [0x80, 0, 1, 2, 3, 4, 5, 0x80, 6, 7, 8, 9, 10, 11, 0x80, 12];
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+const HEX_HI: [u8;16] = [13, 14, 15, 16, 17, 0x80, 18, 19, 20, 21, 22, 23, 0x80, 0x80, 0x80, 0x80];
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+const HASH_LO: [u8;16] = [b'#', 0, 0, 0, 0, 0, 0, b'#', 0, 0, 0, 0, 0, 0, b'#', 0];
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+const HASH_HI: [u8;16] = [0, 0, 0, 0, 0, b'#', 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+const DIGITS: &[u8;16] = b"0123456789abcdef";
+
+/// Groups of 4 pixels that can be loaded 16 bytes at a time and stored
+///   28 bytes at a time
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+fn hex_groups(src: &[u8], out: &[u8]) -> usize {
+    (src.len().saturating_sub(4) / 12).min(out.len() / 28)
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use std::arch::x86_64::*;
+    use std::ptr;
+
+    use super::{hex_groups, DIGITS, HASH_HI, HASH_LO, HEX_HI, HEX_LO};
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn srgb8_to_linear(src: &[u8], out: &mut [f32], table: &[f32;256]) -> usize {
+        let n = src.len().min(out.len()) / 8 * 8;
+        for i in (0..n).step_by(8) {
+            let b = _mm_loadl_epi64(src.as_ptr().add(i) as *const __m128i);
+            let v = _mm256_i32gather_ps::<4>(table.as_ptr(), _mm256_cvtepu8_epi32(b));
+            _mm256_storeu_ps(out.as_mut_ptr().add(i), v);
+        }
+        n
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn linear_to_srgb8(src: &[f32], out: &mut [u8], lut: &[i32], threshold: &[f32;257]) -> usize {
+        let n = src.len().min(out.len()) / 8 * 8;
+        let zero = _mm256_setzero_ps();
+        let one = _mm256_set1_ps(1.0);
+        let scale = _mm256_set1_ps(lut.len() as f32);
+        let last = _mm256_set1_epi32(lut.len() as i32 - 1);
+        for i in (0..n).step_by(8) {
+            // max first, so NaN becomes 0 as with the scalar cast
+            let x = _mm256_min_ps(_mm256_max_ps(_mm256_loadu_ps(src.as_ptr().add(i)), zero), one);
+            let j = _mm256_min_epi32(_mm256_cvttps_epi32(_mm256_mul_ps(x, scale)), last);
+            let k = _mm256_i32gather_epi32::<4>(lut.as_ptr(), j);
+            let t = _mm256_i32gather_ps::<4>(threshold.as_ptr().add(1), k);
+            // Comparison masks are -1 where true
+            let k = _mm256_sub_epi32(k, _mm256_castps_si256(_mm256_cmp_ps::<_CMP_GE_OQ>(x, t)));
+            let w = _mm_packus_epi32(_mm256_castsi256_si128(k), _mm256_extracti128_si256::<1>(k));
+            _mm_storel_epi64(out.as_mut_ptr().add(i) as *mut __m128i, _mm_packus_epi16(w, w));
+        }
+        n
+    }
+
+    /// Shuffle control selecting lanes w and x from the first operand
+    ///   and y and z from the second, as _MM_SHUFFLE
+    const fn shuffle(z: i32, y: i32, x: i32, w: i32) -> i32 {
+        (z << 6) | (y << 4) | (x << 2) | w
+    }
+
+    /// Split 8 packed triples into planes
+    #[target_feature(enable = "avx2")]
+    unsafe fn load3(p: *const f32) -> (__m256, __m256, __m256) {
+        let half = |a: usize, b: usize| {
+            _mm256_insertf128_ps::<1>(_mm256_castps128_ps256(_mm_loadu_ps(p.add(a))), _mm_loadu_ps(p.add(b)))
+        };
+        let (m03, m14, m25) = (half(0, 12), half(4, 16), half(8, 20));
+        let xy = _mm256_shuffle_ps::<{ shuffle(2, 1, 3, 2) }>(m14, m25);
+        let yz = _mm256_shuffle_ps::<{ shuffle(1, 0, 2, 1) }>(m03, m14);
+        (_mm256_shuffle_ps::<{ shuffle(2, 0, 3, 0) }>(m03, xy),
+         _mm256_shuffle_ps::<{ shuffle(3, 1, 2, 0) }>(yz, xy),
+         _mm256_shuffle_ps::<{ shuffle(3, 0, 3, 1) }>(yz, m25))
+    }
+
+    /// Join planes into 8 packed triples
+    #[target_feature(enable = "avx2")]
+    unsafe fn store3(p: *mut f32, x: __m256, y: __m256, z: __m256) {
+        let xy = _mm256_shuffle_ps::<{ shuffle(2, 0, 2, 0) }>(x, y);
+        let yz = _mm256_shuffle_ps::<{ shuffle(3, 1, 3, 1) }>(y, z);
+        let zx = _mm256_shuffle_ps::<{ shuffle(3, 1, 2, 0) }>(z, x);
+        let m03 = _mm256_shuffle_ps::<{ shuffle(2, 0, 2, 0) }>(xy, zx);
+        let m14 = _mm256_shuffle_ps::<{ shuffle(3, 1, 2, 0) }>(yz, xy);
+        let m25 = _mm256_shuffle_ps::<{ shuffle(3, 1, 3, 1) }>(zx, yz);
+        for &(m, a, b) in [(m03, 0, 12), (m14, 4, 16), (m25, 8, 20)].iter() {
+            _mm_storeu_ps(p.add(a), _mm256_castps256_ps128(m));
+            _mm_storeu_ps(p.add(b), _mm256_extractf128_ps::<1>(m));
+        }
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn rgb_to_hsv(src: &[f32], out: &mut [f32]) -> usize {
+        let n = src.len().min(out.len()) / 24 * 24;
+        let zero = _mm256_setzero_ps();
+        for i in (0..n).step_by(24) {
+            let (r, g, b) = load3(src.as_ptr().add(i));
+            let max = _mm256_max_ps(_mm256_max_ps(r, g), b);
+            let d = _mm256_sub_ps(max, _mm256_min_ps(_mm256_min_ps(r, g), b));
+            let hr = _mm256_div_ps(_mm256_sub_ps(g, b), d);
+            let hg = _mm256_add_ps(_mm256_set1_ps(2.0), _mm256_div_ps(_mm256_sub_ps(b, r), d));
+            let hb = _mm256_add_ps(_mm256_set1_ps(4.0), _mm256_div_ps(_mm256_sub_ps(r, g), d));
+            let h = _mm256_blendv_ps(hb, hg, _mm256_cmp_ps::<_CMP_GE_OQ>(g, max));
+            let h = _mm256_blendv_ps(h, hr, _mm256_cmp_ps::<_CMP_GE_OQ>(r, max));
+            let h = _mm256_mul_ps(_mm256_set1_ps(60.0), h);
+            let h = _mm256_blendv_ps(h, _mm256_add_ps(h, _mm256_set1_ps(360.0)),
+                                     _mm256_cmp_ps::<_CMP_LT_OQ>(h, zero));
+            let gray = _mm256_cmp_ps::<_CMP_LT_OQ>(d, _mm256_set1_ps(1e-5));
+            store3(out.as_mut_ptr().add(i),
+                   _mm256_blendv_ps(h, zero, gray),
+                   _mm256_blendv_ps(_mm256_div_ps(d, max), zero, gray),
+                   max);
+        }
+        n
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn hsv_to_rgb(src: &[f32], out: &mut [f32]) -> usize {
+        let n = src.len().min(out.len()) / 24 * 24;
+        let six = _mm256_set1_ps(6.0);
+        for i in (0..n).step_by(24) {
+            let (h, s, v) = load3(src.as_ptr().add(i));
+            let h = _mm256_div_ps(h, _mm256_set1_ps(60.0));
+            let vs = _mm256_mul_ps(v, s);
+            let f = |n: f32| {
+                let k = _mm256_add_ps(_mm256_set1_ps(n), h);
+                let k = _mm256_blendv_ps(k, _mm256_sub_ps(k, six), _mm256_cmp_ps::<_CMP_GE_OQ>(k, six));
+                let c = _mm256_min_ps(k, _mm256_sub_ps(_mm256_set1_ps(4.0), k));
+                let c = _mm256_min_ps(_mm256_max_ps(c, _mm256_setzero_ps()), _mm256_set1_ps(1.0));
+                _mm256_sub_ps(v, _mm256_mul_ps(vs, c))
+            };
+            store3(out.as_mut_ptr().add(i), f(5.0), f(3.0), f(1.0));
+        }
+        n
+    }
+
+    #[target_feature(enable = "ssse3")]
+    pub unsafe fn rgb8_to_hex(src: &[u8], out: &mut [u8]) -> usize {
+        let groups = hex_groups(src, out);
+        let load = |a: &[u8;16]| _mm_loadu_si128(a.as_ptr() as *const __m128i);
+        let (digits, nibble) = (load(DIGITS), _mm_set1_epi8(0x0f));
+        let (lo, hi, hash_lo, hash_hi) = (load(&HEX_LO), load(&HEX_HI), load(&HASH_LO), load(&HASH_HI));
+        // Indices past 15 select from the second block of digits
+        let hi_a = _mm_or_si128(hi, _mm_cmpgt_epi8(hi, _mm_set1_epi8(15)));
+        let hi_b = _mm_or_si128(_mm_sub_epi8(hi, _mm_set1_epi8(16)), _mm_cmplt_epi8(hi, _mm_set1_epi8(16)));
+        for g in 0..groups {
+            let v = _mm_loadu_si128(src.as_ptr().add(12 * g) as *const __m128i);
+            let dh = _mm_shuffle_epi8(digits, _mm_and_si128(_mm_srli_epi16::<4>(v), nibble));
+            let dl = _mm_shuffle_epi8(digits, _mm_and_si128(v, nibble));
+            let a = _mm_unpacklo_epi8(dh, dl);
+            let b = _mm_unpackhi_epi8(dh, dl);
+            let first = _mm_or_si128(_mm_shuffle_epi8(a, lo), hash_lo);
+            let second = _mm_or_si128(_mm_or_si128(_mm_shuffle_epi8(a, hi_a), _mm_shuffle_epi8(b, hi_b)),
+                                      hash_hi);
+            let p = out.as_mut_ptr().add(28 * g);
+            _mm_storeu_si128(p as *mut __m128i, first);
+            _mm_storel_epi64(p.add(16) as *mut __m128i, second);
+            ptr::write_unaligned(p.add(24) as *mut i32, _mm_cvtsi128_si32(_mm_srli_si128::<8>(second)));
+        }
+        4 * groups
+    }
+}
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+mod neon {
+    use std::arch::aarch64::*;
+
+    use super::{hex_groups, DIGITS, HASH_HI, HASH_LO, HEX_HI, HEX_LO};
+
+    pub unsafe fn rgb_to_hsv(src: &[f32], out: &mut [f32]) -> usize {
+        let n = src.len().min(out.len()) / 12 * 12;
+        let zero = vdupq_n_f32(0.0);
+        for i in (0..n).step_by(12) {
+            let p = vld3q_f32(src.as_ptr().add(i));
+            let (r, g, b) = (p.0, p.1, p.2);
+            let max = vmaxq_f32(vmaxq_f32(r, g), b);
+            let d = vsubq_f32(max, vminq_f32(vminq_f32(r, g), b));
+            let hr = vdivq_f32(vsubq_f32(g, b), d);
+            let hg = vaddq_f32(vdupq_n_f32(2.0), vdivq_f32(vsubq_f32(b, r), d));
+            let hb = vaddq_f32(vdupq_n_f32(4.0), vdivq_f32(vsubq_f32(r, g), d));
+            let h = vbslq_f32(vcgeq_f32(g, max), hg, hb);
+            let h = vbslq_f32(vcgeq_f32(r, max), hr, h);
+            let h = vmulq_f32(vdupq_n_f32(60.0), h);
+            let h = vbslq_f32(vcltq_f32(h, zero), vaddq_f32(h, vdupq_n_f32(360.0)), h);
+            let gray = vcltq_f32(d, vdupq_n_f32(1e-5));
+            vst3q_f32(out.as_mut_ptr().add(i),
+                      float32x4x3_t(vbslq_f32(gray, zero, h),
+                                    vbslq_f32(gray, zero, vdivq_f32(d, max)),
+                                    max));
+        }
+        n
+    }
+
+    pub unsafe fn hsv_to_rgb(src: &[f32], out: &mut [f32]) -> usize {
+        let n = src.len().min(out.len()) / 12 * 12;
+        let six = vdupq_n_f32(6.0);
+        for i in (0..n).step_by(12) {
+            let p = vld3q_f32(src.as_ptr().add(i));
+            let (h, s, v) = (vdivq_f32(p.0, vdupq_n_f32(60.0)), p.1, p.2);
+            let vs = vmulq_f32(v, s);
+            let f = |n: f32| {
+                let k = vaddq_f32(vdupq_n_f32(n), h);
+                let k = vbslq_f32(vcgeq_f32(k, six), vsubq_f32(k, six), k);
+                let c = vminq_f32(k, vsubq_f32(vdupq_n_f32(4.0), k));
+                let c = vminq_f32(vmaxq_f32(c, vdupq_n_f32(0.0)), vdupq_n_f32(1.0));
+                vsubq_f32(v, vmulq_f32(vs, c))
+            };
+            vst3q_f32(out.as_mut_ptr().add(i), float32x4x3_t(f(5.0), f(3.0), f(1.0)));
+        }
+        n
+    }
+
+    pub unsafe fn rgb8_to_hex(src: &[u8], out: &mut [u8]) -> usize {
+        let groups = hex_groups(src, out);
+        let (digits, nibble) = (vld1q_u8(DIGITS.as_ptr()), vdupq_n_u8(0x0f));
+        let (lo, hi) = (vld1q_u8(HEX_LO.as_ptr()), vld1q_u8(HEX_HI.as_ptr()));
+        let (hash_lo, hash_hi) = (vld1q_u8(HASH_LO.as_ptr()), vld1q_u8(HASH_HI.as_ptr()));
+        for g in 0..groups {
+            let v = vld1q_u8(src.as_ptr().add(12 * g));
+            let dh = vqtbl1q_u8(digits, vshrq_n_u8::<4>(v));
+            let dl = vqtbl1q_u8(digits, vandq_u8(v, nibble));
+            let chars = uint8x16x2_t(vzip1q_u8(dh, dl), vzip2q_u8(dh, dl));
+            let first = vorrq_u8(vqtbl2q_u8(chars, lo), hash_lo);
+            let second = vorrq_u8(vqtbl2q_u8(chars, hi), hash_hi);
+            let p = out.as_mut_ptr().add(28 * g);
+            vst1q_u8(p, first);
+            let mut tail = [0u8; 16];
+            vst1q_u8(tail.as_mut_ptr(), second);
+            std::ptr::copy_nonoverlapping(tail.as_ptr(), p.add(16), 12);
+        }
+        4 * groups
+    }
+}
@@ -0,0 +1,523 @@
+//! Color spaces beyond RGB, HSV, HSL and YIQ
+//!
+//! Conversions follow the CSS Color 4 specification:
+//! https://www.w3.org/TR/css-color-4/#color-conversion-code
+//!
+//! Lab and LCH use a D50 white point, as in CSS, with Bradford
+//! chromatic adaptation from the D65 white point of sRGB.
+//! Oklab and Oklch are defined relative to D65:
+//! https://bottosson.github.io/posts/oklab/
+
+use Color;
+
+/// Predefined RGB and XYZ color spaces
+///
+/// These are the spaces available to the CSS color() function
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Hash)]
+pub enum ColorSpace {
+    /// sRGB, the space of Color itself
+    Srgb,
+    /// sRGB with a linear transfer function
+    SrgbLinear,
+    /// Display P3, wide gamut with sRGB transfer function
+    DisplayP3,
+    /// Adobe RGB (1998) compatible
+    A98Rgb,
+    /// ProPhoto RGB, D50 white point
+    ProphotoRgb,
+    /// ITU-R BT.2020
+    Rec2020,
+    /// CIE XYZ relative to a D50 white point
+    XyzD50,
+    /// CIE XYZ relative to a D65 white point
+    XyzD65,
+}
+
+impl ColorSpace {
+    /// Name used by the CSS color() function
+    ///
+    /// ```
+    /// # use tint::ColorSpace;
+    /// assert_eq!(ColorSpace::DisplayP3.css_name(), "display-p3");
+    /// ```
+    pub fn css_name(&self) -> &'static str {
+        match *self {
+            ColorSpace::Srgb        => "srgb",
+            ColorSpace::SrgbLinear  => "srgb-linear",
+            ColorSpace::DisplayP3   => "display-p3",
+            ColorSpace::A98Rgb      => "a98-rgb",
+            ColorSpace::ProphotoRgb => "prophoto-rgb",
+            ColorSpace::Rec2020     => "rec2020",
+            ColorSpace::XyzD50      => "xyz-d50",
+            ColorSpace::XyzD65      => "xyz-d65",
+        }
+    }
+    /// Look up a color space from its CSS color() name, ignoring case
+    ///
+    ///   "xyz" is an alias for "xyz-d65"
+    ///
+    /// ```
+    /// # use tint::ColorSpace;
+    /// assert_eq!(ColorSpace::from_css_name("Rec2020"), Some(ColorSpace::Rec2020));
+    /// assert_eq!(ColorSpace::from_css_name("xyz"), Some(ColorSpace::XyzD65));
+    /// ```
+    pub fn from_css_name(name: &str) -> Option<ColorSpace> {
+        let space = match name.to_lowercase().as_str() {
+            "srgb"         => ColorSpace::Srgb,
+            "srgb-linear"  => ColorSpace::SrgbLinear,
+            "display-p3"   => ColorSpace::DisplayP3,
+            "a98-rgb"      => ColorSpace::A98Rgb,
+            "prophoto-rgb" => ColorSpace::ProphotoRgb,
+            "rec2020"      => ColorSpace::Rec2020,
+            "xyz-d50"      => ColorSpace::XyzD50,
+            "xyz-d65" | "xyz" => ColorSpace::XyzD65,
+            _ => return None,
+        };
+        Some(space)
+    }
+}
+
+impl Color {
+    /// Convert Color to linear light sRGB
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let (r,g,b) = Color::from_rgb1(1.0, 0.5, 0.0).to_linear_rgb();
+    /// assert_eq!((r,b), (1.0, 0.0));
+    /// assert!((g - 0.214).abs() < 1e-3);
+    /// ```
+    pub fn to_linear_rgb(&self) -> (f64,f64,f64) {
+        (srgb_to_linear(self.red), srgb_to_linear(self.green), srgb_to_linear(self.blue))
+    }
+    /// Convert Color to linear light sRGB and alpha as f32
+    ///
+    ///   Shaders and GPU blending expect linear values; alpha is not
+    ///   transformed
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let v = Color::new(1.0, 0.5, 0.0, 0.5).to_linear_f32_array();
+    /// assert_eq!((v[0], v[2], v[3]), (1.0, 0.0, 0.5));
+    /// assert!((v[1] - 0.214).abs() < 1e-3);
+    /// ```
+    pub fn to_linear_f32_array(&self) -> [f32;4] {
+        let (r,g,b) = self.to_linear_rgb();
+        [r as f32, g as f32, b as f32, self.alpha as f32]
+    }
+    /// Create new Color from linear light sRGB
+    ///   alpha value set to 1.0
+    pub fn from_linear_rgb(r: f64, g: f64, b: f64) -> Color {
+        Color::from_rgb1(linear_to_srgb(r), linear_to_srgb(g), linear_to_srgb(b))
+    }
+    /// Convert Color to CIE XYZ, D65 white point
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let (x,y,z) = Color::from_rgb1(1.0, 1.0, 1.0).to_xyz();
+    /// assert!((x - 0.9505).abs() < 1e-4);
+    /// assert!((y - 1.0000).abs() < 1e-4);
+    /// assert!((z - 1.0891).abs() < 1e-4);
+    /// ```
+    pub fn to_xyz(&self) -> (f64,f64,f64) {
+        mul3(&SRGB_TO_XYZ, self.to_linear_rgb())
+    }
+    /// Create new Color from CIE XYZ, D65 white point
+    ///   alpha value set to 1.0
+    pub fn from_xyz(x: f64, y: f64, z: f64) -> Color {
+        let (r,g,b) = mul3(&XYZ_TO_SRGB, (x,y,z));
+        Color::from_linear_rgb(r, g, b)
+    }
+    /// Convert Color to CIE Lab, D50 white point
+    ///
+    ///   L : [0, 100]
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let (l,a,b) = Color::from_rgb255(255, 0, 0).to_lab();
+    /// assert!((l - 54.29).abs() < 1e-2);
+    /// assert!((a - 80.80).abs() < 1e-2);
+    /// assert!((b - 69.89).abs() < 1e-2);
+    /// ```
+    pub fn to_lab(&self) -> (f64,f64,f64) {
+        xyz2lab(mul3(&D65_TO_D50, self.to_xyz()))
+    }
+    /// Create new Color from CIE Lab, D50 white point
+    ///   alpha value set to 1.0
+    ///
+    ///   Results outside of the sRGB gamut are not clipped
+    pub fn from_lab(l: f64, a: f64, b: f64) -> Color {
+        let (x,y,z) = mul3(&D50_TO_D65, lab2xyz((l,a,b)));
+        Color::from_xyz(x, y, z)
+    }
+    /// Convert Color to CIE LCH, D50 white point
+    ///
+    ///   L : [0, 100],  h : [0, 360)
+    pub fn to_lch(&self) -> (f64,f64,f64) {
+        lab2lch(self.to_lab())
+    }
+    /// Create new Color from CIE LCH, D50 white point
+    ///   alpha value set to 1.0
+    pub fn from_lch(l: f64, c: f64, h: f64) -> Color {
+        let (l,a,b) = lch2lab((l,c,h));
+        Color::from_lab(l, a, b)
+    }
+    /// Convert Color to Oklab
+    ///
+    ///   L : [0, 1]
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let (l,a,b) = Color::from_rgb1(1.0, 1.0, 1.0).to_oklab();
+    /// assert!((l - 1.0).abs() < 1e-6);
+    /// assert!(a.abs() < 1e-6 && b.abs() < 1e-6);
+    /// ```
+    pub fn to_oklab(&self) -> (f64,f64,f64) {
+        linear2oklab(self.to_linear_rgb())
+    }
+    /// Create new Color from Oklab
+    ///   alpha value set to 1.0
+    pub fn from_oklab(l: f64, a: f64, b: f64) -> Color {
+        let (r,g,b) = oklab2linear((l,a,b));
+        Color::from_linear_rgb(r, g, b)
+    }
+    /// Convert Color to Oklch
+    ///
+    ///   L : [0, 1],  h : [0, 360)
+    pub fn to_oklch(&self) -> (f64,f64,f64) {
+        lab2lch(self.to_oklab())
+    }
+    /// Create new Color from Oklch
+    ///   alpha value set to 1.0
+    pub fn from_oklch(l: f64, c: f64, h: f64) -> Color {
+        let (l,a,b) = lch2lab((l,c,h));
+        Color::from_oklab(l, a, b)
+    }
+    /// Convert Color to one of the predefined color spaces
+    ///
+    /// ```
+    /// # use tint::{Color, ColorSpace};
+    /// let (r,g,b) = Color::from_rgb1(1.0, 0.0, 0.0).to_color_space(ColorSpace::DisplayP3);
+    /// assert!((r - 0.9175).abs() < 1e-4);
+    /// assert!((g - 0.2003).abs() < 1e-4);
+    /// assert!((b - 0.1386).abs() < 1e-4);
+    /// ```
+    pub fn to_color_space(&self, space: ColorSpace) -> (f64,f64,f64) {
+        let xyz = self.to_xyz();
+        match space {
+            ColorSpace::Srgb => self.to_rgb1(),
+            ColorSpace::SrgbLinear => self.to_linear_rgb(),
+            ColorSpace::DisplayP3 =>
+                map3(mul3(&XYZ_TO_P3, xyz), linear_to_srgb),
+            ColorSpace::A98Rgb =>
+                map3(mul3(&XYZ_TO_A98, xyz), linear_to_a98),
+            ColorSpace::ProphotoRgb =>
+                map3(mul3(&XYZ_D50_TO_PROPHOTO, mul3(&D65_TO_D50, xyz)), linear_to_prophoto),
+            ColorSpace::Rec2020 =>
+                map3(mul3(&XYZ_TO_REC2020, xyz), linear_to_rec2020),
+            ColorSpace::XyzD50 => mul3(&D65_TO_D50, xyz),
+            ColorSpace::XyzD65 => xyz,
+        }
+    }
+    /// Create new Color from one of the predefined color spaces
+    ///   alpha value set to 1.0
+    ///
+    ///   Results outside of the sRGB gamut are not clipped
+    ///
+    /// ```
+    /// # use tint::{Color, ColorSpace};
+    /// let red = Color::from_rgb1(1.0, 0.0, 0.0);
+    /// let (r,g,b) = red.to_color_space(ColorSpace::Rec2020);
+    /// let c = Color::from_color_space(ColorSpace::Rec2020, r, g, b);
+    /// assert!((c.red - 1.0).abs() < 1e-9);
+    /// assert!(c.green.abs() < 1e-9 && c.blue.abs() < 1e-9);
+    /// ```
+    pub fn from_color_space(space: ColorSpace, c0: f64, c1: f64, c2: f64) -> Color {
+        let c = (c0, c1, c2);
+        let xyz = match space {
+            ColorSpace::Srgb => return Color::from_rgb1(c0, c1, c2),
+            ColorSpace::SrgbLinear => return Color::from_linear_rgb(c0, c1, c2),
+            ColorSpace::DisplayP3 =>
+                mul3(&P3_TO_XYZ, map3(c, srgb_to_linear)),
+            ColorSpace::A98Rgb =>
+                mul3(&A98_TO_XYZ, map3(c, a98_to_linear)),
+            ColorSpace::ProphotoRgb =>
+                mul3(&D50_TO_D65, mul3(&PROPHOTO_TO_XYZ_D50, map3(c, prophoto_to_linear))),
+            ColorSpace::Rec2020 =>
+                mul3(&REC2020_TO_XYZ, map3(c, rec2020_to_linear)),
+            ColorSpace::XyzD50 => mul3(&D50_TO_D65, c),
+            ColorSpace::XyzD65 => c,
+        };
+        Color::from_xyz(xyz.0, xyz.1, xyz.2)
+    }
+}
+
+type Mat3 = [[f64;3];3];
+
+pub(crate) fn mul3(m: &Mat3, v: (f64,f64,f64)) -> (f64,f64,f64) {
+    (m[0][0] * v.0 + m[0][1] * v.1 + m[0][2] * v.2,
+     m[1][0] * v.0 + m[1][1] * v.1 + m[1][2] * v.2,
+     m[2][0] * v.0 + m[2][1] * v.1 + m[2][2] * v.2)
+}
+
+fn map3<F>(v: (f64,f64,f64), f: F) -> (f64,f64,f64)
+    where F: Fn(f64) -> f64
+{
+    (f(v.0), f(v.1), f(v.2))
+}
+
+// Transfer functions, extended to negative values by symmetry
+
+pub(crate) fn srgb_to_linear(v: f64) -> f64 {
+    let a = v.abs();
+    if a <= 0.04045 {
+        v / 12.92
+    } else {
+        v.signum() * ((a + 0.055) / 1.055).powf(2.4)
+    }
+}
+pub(crate) fn linear_to_srgb(v: f64) -> f64 {
+    let a = v.abs();
+    if a <= 0.0031308 {
+        v * 12.92
+    } else {
+        v.signum() * (1.055 * a.powf(1.0 / 2.4) - 0.055)
+    }
+}
+fn a98_to_linear(v: f64) -> f64 {
+    v.signum() * v.abs().powf(563.0 / 256.0)
+}
+fn linear_to_a98(v: f64) -> f64 {
+    v.signum() * v.abs().powf(256.0 / 563.0)
+}
+fn prophoto_to_linear(v: f64) -> f64 {
+    let a = v.abs();
+    if a <= 16.0 / 512.0 {
+        v / 16.0
+    } else {
+        v.signum() * a.powf(1.8)
+    }
+}
+fn linear_to_prophoto(v: f64) -> f64 {
+    let a = v.abs();
+    if a >= 1.0 / 512.0 {
+        v.signum() * a.powf(1.0 / 1.8)
+    } else {
+        16.0 * v
+    }
+}
+const REC2020_ALPHA: f64 = 1.099_296_826_809_44;
+const REC2020_BETA: f64 = 0.018_053_968_510_807;
+fn rec2020_to_linear(v: f64) -> f64 {
+    let a = v.abs();
+    if a < REC2020_BETA * 4.5 {
+        v / 4.5
+    } else {
+        v.signum() * ((a + REC2020_ALPHA - 1.0) / REC2020_ALPHA).powf(1.0 / 0.45)
+    }
+}
+fn linear_to_rec2020(v: f64) -> f64 {
+    let a = v.abs();
+    if a > REC2020_BETA {
+        v.signum() * (REC2020_ALPHA * a.powf(0.45) - (REC2020_ALPHA - 1.0))
+    } else {
+        4.5 * v
+    }
+}
+
+// Lab, D50
+
+const D50_WHITE: (f64,f64,f64) = (0.3457 / 0.3585, 1.0, (1.0 - 0.3457 - 0.3585) / 0.3585);
+
+const LAB_E: f64 = 216.0 / 24389.0;
+const LAB_K: f64 = 24389.0 / 27.0;
+
+pub(crate) fn xyz2lab(xyz: (f64,f64,f64)) -> (f64,f64,f64) {
+    let f = |v: f64| if v > LAB_E { v.cbrt() } else { (LAB_K * v + 16.0) / 116.0 };
+    let fx = f(xyz.0 / D50_WHITE.0);
+    let fy = f(xyz.1 / D50_WHITE.1);
+    let fz = f(xyz.2 / D50_WHITE.2);
+    (116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+}
+pub(crate) fn lab2xyz(lab: (f64,f64,f64)) -> (f64,f64,f64) {
+    let (l,a,b) = lab;
+    let fy = (l + 16.0) / 116.0;
+    let fx = a / 500.0 + fy;
+    let fz = fy - b / 200.0;
+    let x = if fx.powi(3) > LAB_E { fx.powi(3) } else { (116.0 * fx - 16.0) / LAB_K };
+    let y = if l > LAB_K * LAB_E { fy.powi(3) } else { l / LAB_K };
+    let z = if fz.powi(3) > LAB_E { fz.powi(3) } else { (116.0 * fz - 16.0) / LAB_K };
+    (x * D50_WHITE.0, y * D50_WHITE.1, z * D50_WHITE.2)
+}
+
+/// Rectangular to polar form, hue in degrees [0, 360)
+pub(crate) fn lab2lch(lab: (f64,f64,f64)) -> (f64,f64,f64) {
+    let (l,a,b) = lab;
+    let c = (a * a + b * b).sqrt();
+    let h = if c < 1e-10 { 0.0 } else { b.atan2(a).to_degrees().rem_euclid(360.0) };
+    (l, c, h)
+}
+/// Polar to rectangular form, hue in degrees
+pub(crate) fn lch2lab(lch: (f64,f64,f64)) -> (f64,f64,f64) {
+    let (l,c,h) = lch;
+    let h = h.to_radians();
+    (l, c * h.cos(), c * h.sin())
+}
+
+// Oklab, from linear sRGB
+
+pub(crate) fn linear2oklab(rgb: (f64,f64,f64)) -> (f64,f64,f64) {
+    let (r,g,b) = rgb;
+    let l = 0.412_221_470_8 * r + 0.536_332_536_3 * g + 0.051_445_992_9 * b;
+    let m = 0.211_903_498_2 * r + 0.680_699_545_1 * g + 0.107_396_956_6 * b;
+    let s = 0.088_302_461_9 * r + 0.281_718_837_6 * g + 0.629_978_700_5 * b;
+    let (l, m, s) = (l.cbrt(), m.cbrt(), s.cbrt());
+    (0.210_454_255_3 * l + 0.793_617_785_0 * m - 0.004_072_046_8 * s,
+     1.977_998_495_1 * l - 2.428_592_205_0 * m + 0.450_593_709_9 * s,
+     0.025_904_037_1 * l + 0.782_771_766_2 * m - 0.808_675_766_0 * s)
+}
+pub(crate) fn oklab2linear(lab: (f64,f64,f64)) -> (f64,f64,f64) {
+    let (ll,a,b) = lab;
+    let l = ll + 0.396_337_777_4 * a + 0.215_803_757_3 * b;
+    let m = ll - 0.105_561_345_8 * a - 0.063_854_172_8 * b;
+    let s = ll - 0.089_484_177_5 * a - 1.291_485_548_0 * b;
+    let (l, m, s) = (l.powi(3), m.powi(3), s.powi(3));
+    ( 4.076_741_662_1 * l - 3.307_711_591_3 * m + 0.230_969_929_2 * s,
+     -1.268_438_004_6 * l + 2.609_757_401_1 * m - 0.341_319_396_5 * s,
+     -0.004_196_086_3 * l - 0.703_418_614_7 * m + 1.707_614_701_0 * s)
+}
+
+// Matrices, from CSS Color 4
+
+pub(crate) const SRGB_TO_XYZ: Mat3 = [
+    [506752.0 / 1228815.0,  87881.0 / 245763.0,   12673.0 /   70218.0],
+    [ 87098.0 /  409605.0, 175762.0 / 245763.0,   12673.0 /  175545.0],
+    [  7918.0 /  409605.0,  87881.0 / 737289.0, 1001167.0 / 1053270.0],
+];
+pub(crate) const XYZ_TO_SRGB: Mat3 = [
+    [  12831.0 /   3959.0,    -329.0 /    214.0, -1974.0 /   3959.0],
+    [-851781.0 / 878810.0, 1648619.0 / 878810.0, 36519.0 / 878810.0],
+    [    705.0 /  12673.0,   -2585.0 /  12673.0,   705.0 /    667.0],
+];
+pub(crate) const D65_TO_D50: Mat3 = [
+    [ 1.047_929_792_544_996_9,   0.022_946_870_601_609_652, -0.050_192_266_289_205_24 ],
+    [ 0.029_627_808_770_055_99,  0.990_434_426_753_879_9,   -0.017_073_799_063_418_826],
+    [-0.009_243_040_646_204_504, 0.015_055_191_490_298_152,  0.751_874_281_428_137_1  ],
+];
+pub(crate) const D50_TO_D65: Mat3 = [
+    [ 0.955_473_421_488_075,    -0.023_098_454_948_764_71,  0.063_259_243_200_570_72 ],
+    [-0.028_369_709_333_863_7,   1.009_995_398_081_304_1,   0.021_041_441_191_917_323],
+    [ 0.012_314_014_864_481_998,-0.020_507_649_298_898_964, 1.330_365_926_242_124    ],
+];
+const P3_TO_XYZ: Mat3 = [
+    [608311.0 / 1250200.0, 189793.0 / 714400.0,  198249.0 / 1000160.0],
+    [ 35783.0 /  156275.0, 247089.0 / 357200.0,  198249.0 / 2500400.0],
+    [      0.0,             32229.0 / 714400.0, 5220557.0 / 5000800.0],
+];
+const XYZ_TO_P3: Mat3 = [
+    [446124.0 / 178915.0, -333277.0 / 357830.0, -72051.0 / 178915.0],
+    [-14852.0 /  17905.0,   63121.0 /  35810.0,    423.0 /  17905.0],
+    [ 11844.0 / 330415.0,  -50337.0 / 660830.0, 316169.0 / 330415.0],
+];
+const A98_TO_XYZ: Mat3 = [
+    [573536.0 /  994567.0,  263643.0 / 1420810.0,  187206.0 /  994567.0],
+    [591459.0 / 1989134.0, 6239551.0 / 9945670.0,  374412.0 / 4972835.0],
+    [ 53769.0 / 1989134.0,  351524.0 / 4972835.0, 4929758.0 / 4972835.0],
+];
+const XYZ_TO_A98: Mat3 = [
+    [1829569.0 /  896150.0, -506331.0 /  896150.0, -308931.0 /  896150.0],
+    [-851781.0 /  878810.0, 1648619.0 /  878810.0,   36519.0 /  878810.0],
+    [  16779.0 / 1248040.0, -147721.0 / 1248040.0, 1266979.0 / 1248040.0],
+];
+const PROPHOTO_TO_XYZ_D50: Mat3 = [
+    [0.797_766_644_900_642_3, 0.135_181_297_400_533_08, 0.031_347_734_128_392_2  ],
+    [0.288_074_828_819_401_3, 0.711_835_234_241_873,    0.000_089_936_938_725_64 ],
+    [0.0,                     0.0,                      0.825_104_602_510_460_2  ],
+];
+const XYZ_D50_TO_PROPHOTO: Mat3 = [
+    [ 1.345_786_881_647_158_3, -0.255_572_087_379_794_64, -0.051_101_864_975_545_26],
+    [-0.544_630_705_124_901_9,  1.508_247_742_845_146_8,   0.020_527_447_436_421_39],
+    [ 0.0,                      0.0,                       1.211_967_545_638_945_2 ],
+];
+const REC2020_TO_XYZ: Mat3 = [
+    [63426534.0 / 99577255.0,  20160776.0 / 139408157.0,  47086771.0 / 278816314.0],
+    [26158966.0 / 99577255.0, 472592308.0 / 697040785.0,   8267143.0 / 139408157.0],
+    [       0.0,               19567812.0 / 697040785.0, 295819943.0 / 278816314.0],
+];
+const XYZ_TO_REC2020: Mat3 = [
+    [ 30757411.0 / 17917100.0, -6372589.0 / 17917100.0, -4539589.0 / 17917100.0],
+    [-19765991.0 / 29648200.0, 47925759.0 / 29648200.0,   467509.0 / 29648200.0],
+    [   792561.0 / 44930125.0, -1921689.0 / 44930125.0, 42328811.0 / 44930125.0],
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_tol(a: (f64,f64,f64), b: (f64,f64,f64), tol: f64) {
+        assert!((a.0 - b.0).abs() <= tol && (a.1 - b.1).abs() <= tol && (a.2 - b.2).abs() <= tol,
+                "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn inverse_matrices() {
+        let pairs = [(SRGB_TO_XYZ, XYZ_TO_SRGB), (D65_TO_D50, D50_TO_D65),
+                     (P3_TO_XYZ, XYZ_TO_P3), (A98_TO_XYZ, XYZ_TO_A98),
+                     (PROPHOTO_TO_XYZ_D50, XYZ_D50_TO_PROPHOTO),
+                     (REC2020_TO_XYZ, XYZ_TO_REC2020)];
+        for &(m, inv) in pairs.iter() {
+            for v in [(1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (0.0, 0.0, 1.0)].iter() {
+                assert_tol(mul3(&inv, mul3(&m, *v)), *v, 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn white() {
+        let white = Color::from_rgb1(1.0, 1.0, 1.0);
+        assert_tol(white.to_lab(), (100.0, 0.0, 0.0), 1e-4);
+        assert_tol(white.to_oklab(), (1.0, 0.0, 0.0), 1e-6);
+        for space in [ColorSpace::Srgb, ColorSpace::SrgbLinear, ColorSpace::DisplayP3,
+                      ColorSpace::A98Rgb, ColorSpace::ProphotoRgb, ColorSpace::Rec2020].iter() {
+            assert_tol(white.to_color_space(*space), (1.0, 1.0, 1.0), 1e-4);
+        }
+        assert_tol(white.to_color_space(ColorSpace::XyzD50), D50_WHITE, 1e-4);
+        assert_tol(white.to_color_space(ColorSpace::XyzD65), (0.95046, 1.0, 1.08906), 1e-4);
+    }
+
+    #[test]
+    fn known_values() {
+        // Values from https://colorjs.io
+        let red = Color::from_rgb1(1.0, 0.0, 0.0);
+        assert_tol(red.to_oklab(), (0.62796, 0.22486, 0.12585), 1e-4);
+        assert_tol(red.to_lch(), (54.2905, 106.8372, 40.8577), 1e-3);
+        let blue = Color::from_rgb1(0.0, 0.0, 1.0);
+        assert_tol(blue.to_oklch(), (0.45201, 0.31321, 264.052), 1e-3);
+    }
+
+    #[test]
+    fn round_trip() {
+        let spaces = [ColorSpace::Srgb, ColorSpace::SrgbLinear, ColorSpace::DisplayP3,
+                      ColorSpace::A98Rgb, ColorSpace::ProphotoRgb, ColorSpace::Rec2020,
+                      ColorSpace::XyzD50, ColorSpace::XyzD65];
+        for r in 0..6 {
+            for g in 0..6 {
+                for b in 0..6 {
+                    let c = Color::from_rgb1(r as f64 / 5.0, g as f64 / 5.0, b as f64 / 5.0);
+                    let (l,a,bb) = c.to_lab();
+                    assert_tol(Color::from_lab(l,a,bb).to_rgb1(), c.to_rgb1(), 1e-9);
+                    let (l,ch,h) = c.to_lch();
+                    assert_tol(Color::from_lch(l,ch,h).to_rgb1(), c.to_rgb1(), 1e-9);
+                    let (l,a,bb) = c.to_oklab();
+                    assert_tol(Color::from_oklab(l,a,bb).to_rgb1(), c.to_rgb1(), 1e-5);
+                    let (l,ch,h) = c.to_oklch();
+                    assert_tol(Color::from_oklch(l,ch,h).to_rgb1(), c.to_rgb1(), 1e-5);
+                    for space in spaces.iter() {
+                        let (x,y,z) = c.to_color_space(*space);
+                        let c2 = Color::from_color_space(*space, x, y, z);
+                        assert_tol(c2.to_rgb1(), c.to_rgb1(), 1e-9);
+                    }
+                }
+            }
+        }
+    }
+}
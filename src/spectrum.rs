@@ -0,0 +1,83 @@
+//! Spectral colors
+//!
+//! Wyman, Sloan and Shirley (2013), "Simple Analytic Approximations to
+//! the CIE XYZ Color Matching Functions"
+
+use space::{linear_to_srgb, mul3, XYZ_TO_SRGB};
+use Color;
+
+/// Piecewise Gaussian with different widths either side of the mean
+fn lobe(x: f64, mu: f64, s1: f64, s2: f64) -> f64 {
+    let t = (x - mu) / if x < mu { s1 } else { s2 };
+    (-0.5 * t * t).exp()
+}
+
+/// CIE 1931 2 degree standard observer color matching functions
+fn cmf(nm: f64) -> (f64, f64, f64) {
+    let x = 1.056 * lobe(nm, 599.8, 37.9, 31.0) + 0.362 * lobe(nm, 442.0, 16.0, 26.7)
+        - 0.065 * lobe(nm, 501.1, 20.4, 26.2);
+    let y = 0.821 * lobe(nm, 568.8, 46.9, 40.5) + 0.286 * lobe(nm, 530.9, 16.3, 31.1);
+    let z = 1.217 * lobe(nm, 437.0, 11.8, 36.0) + 0.681 * lobe(nm, 459.0, 26.0, 13.8);
+    (x, y, z)
+}
+
+impl Color {
+    /// Create new Color approximating monochromatic light of a wavelength in nm
+    ///   alpha value set to 1.0
+    ///
+    ///   The hue follows the CIE 1931 standard observer.  Spectral
+    ///   colors lie outside the sRGB gamut, so negative components are
+    ///   clipped and the result scaled to full brightness.  Intensity fades to black from 420 nm to 380 nm
+    ///   and from 700 nm to 780 nm; outside 380 - 780 nm is black
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let (r,g,b) = Color::from_wavelength(650.0).to_rgb255();
+    /// assert!(r > 250 && g < 64 && b < 64);
+    /// let (r,g,b) = Color::from_wavelength(530.0).to_rgb255();
+    /// assert!(g > 250 && r < 128 && b < 128);
+    /// assert_eq!(Color::from_wavelength(300.0).to_hex(), "#000000");
+    /// ```
+    pub fn from_wavelength(nm: f64) -> Color {
+        if !(380.0..=780.0).contains(&nm) {
+            return Color::from_rgb1(0.0, 0.0, 0.0);
+        }
+        // The locus barely moves outside 400 - 650 nm, where the fit is poor
+        let (r,g,b) = mul3(&XYZ_TO_SRGB, cmf(nm.clamp(400.0, 650.0)));
+        let (r,g,b) = (r.max(0.0), g.max(0.0), b.max(0.0));
+        let hi = r.max(g).max(b);
+        if hi <= 0.0 {
+            return Color::from_rgb1(0.0, 0.0, 0.0);
+        }
+        let fade = if nm < 420.0 {
+            (nm - 380.0) / 40.0
+        } else if nm > 700.0 {
+            (780.0 - nm) / 80.0
+        } else {
+            1.0
+        };
+        let f = |v: f64| linear_to_srgb(v / hi * fade);
+        Color::from_rgb1(f(r), f(g), f(b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn wavelength() {
+        let (x,y,z) = cmf(555.0);
+        assert!((y - 1.0).abs() < 0.01);
+        assert!(x > 0.4 && z < 0.01);
+        let red = Color::from_wavelength(720.0);
+        assert!(red.red > 0.5 && red.green == 0.0 && red.blue == 0.0);
+        let blue = Color::from_wavelength(460.0);
+        assert!(blue.blue > blue.red && blue.blue > blue.green);
+        assert_eq!(Color::from_wavelength(380.0).to_hex(), "#000000");
+        assert_eq!(Color::from_wavelength(800.0).to_hex(), "#000000");
+        for nm in 380..=780 {
+            let c = Color::from_wavelength(nm as f64);
+            assert!(c.channels().all(|v| (0.0..=1.0).contains(&v)), "{}", nm);
+        }
+    }
+}
@@ -0,0 +1,163 @@
+//! Color temperature
+
+use Color;
+
+/// Warm or cool classification from Color::warmth_class()
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Hash)]
+pub enum Warmth {
+    /// Reds, oranges and yellows
+    Warm,
+    /// Greens, blues and purples
+    Cool,
+    /// Grays and colors with little chroma, or hues between warm and cool
+    Neutral,
+}
+
+impl Color {
+    /// Create new Color from a blackbody color temperature in Kelvin
+    ///   alpha value set to 1.0
+    ///
+    ///   Uses Tanner Helland's fit to the Planckian locus, valid from
+    ///   1000 K to 40000 K; temperatures outside are clipped.  6600 K
+    ///   is close to white
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// assert_eq!(Color::from_kelvin(2700.0).to_rgb255(), (255, 166, 87));
+    /// assert_eq!(Color::from_kelvin(6600.0).to_rgb255(), (255, 255, 255));
+    /// ```
+    pub fn from_kelvin(kelvin: f64) -> Color {
+        let t = kelvin.clamp(1000.0, 40000.0) / 100.0;
+        let r = if t <= 66.0 {
+            255.0
+        } else {
+            329.698727446 * (t - 60.0).powf(-0.1332047592)
+        };
+        let g = if t <= 66.0 {
+            99.4708025861 * t.ln() - 161.1195681661
+        } else {
+            288.1221695283 * (t - 60.0).powf(-0.0755148492)
+        };
+        let b = if t >= 66.0 {
+            255.0
+        } else if t <= 19.0 {
+            0.0
+        } else {
+            138.5177312231 * (t - 10.0).ln() - 305.0447927307
+        };
+        let f = |v: f64| v.clamp(0.0, 255.0) / 255.0;
+        Color::from_rgb1(f(r), f(g), f(b))
+    }
+    /// Estimate correlated color temperature in Kelvin
+    ///
+    ///   Uses McCamy's cubic approximation from CIE 1931 xy
+    ///   chromaticity, accurate to a few Kelvin from about 2000 K to
+    ///   12500 K for colors near the Planckian locus.  Black returns NaN
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let white = Color::from_rgb255(255, 255, 255);
+    /// assert!((white.cct() - 6504.0).abs() < 5.0);
+    /// ```
+    pub fn cct(&self) -> f64 {
+        let (x, y) = self.xy();
+        let n = (x - 0.3320) / (0.1858 - y);
+        ((449.0 * n + 3525.0) * n + 6823.3) * n + 5520.33
+    }
+    /// Distance from the Planckian locus in CIE 1960 uv, Duv
+    ///
+    ///   Positive values lie above the locus (greenish), negative
+    ///   below (pinkish).  Uses Ohno's polynomial approximation of the
+    ///   locus.  Black returns NaN
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// let white = Color::from_rgb255(255, 255, 255);
+    /// assert!((white.duv() - 0.0032).abs() < 0.0002);
+    /// ```
+    pub fn duv(&self) -> f64 {
+        let (x, y) = self.xy();
+        let d = -2.0 * x + 12.0 * y + 3.0;
+        let (u, v) = (4.0 * x / d, 6.0 * y / d);
+        let lfp = (u - 0.292).hypot(v - 0.24);
+        let a = ((u - 0.292) / lfp).acos();
+        let k = [-0.471106, 1.925865, -2.4243787, 1.5317403,
+                 -0.5179722, 0.0893944, -0.00616793];
+        let lbb = k.iter().rev().fold(0.0, |acc, k| acc * a + k);
+        lfp - lbb
+    }
+    /// Perceived warmth score, from -1 (cool) to 1 (warm)
+    ///
+    ///   Based on the Oklch hue relative to a warm pole at 50 degrees
+    ///   (orange) and a cool pole at 230 degrees (blue-cyan), scaled
+    ///   down for colors with little chroma
+    ///
+    /// ```
+    /// # use tint::Color;
+    /// assert!(Color::from("orange").warmth() > 0.8);
+    /// assert!(Color::from("steelblue").warmth() < -0.5);
+    /// assert!(Color::from("gray").warmth().abs() < 1e-3);
+    /// ```
+    pub fn warmth(&self) -> f64 {
+        let (_, c, h) = self.to_oklch();
+        (h - 50.0).to_radians().cos() * (c / 0.1).min(1.0)
+    }
+    /// Classify the Color as warm, cool or neutral
+    ///
+    ///   Scores from warmth() within 0.2 of zero are neutral
+    ///
+    /// ```
+    /// # use tint::{Color, Warmth};
+    /// assert_eq!(Color::from("red").warmth_class(), Warmth::Warm);
+    /// assert_eq!(Color::from("navy").warmth_class(), Warmth::Cool);
+    /// assert_eq!(Color::from("white").warmth_class(), Warmth::Neutral);
+    /// ```
+    pub fn warmth_class(&self) -> Warmth {
+        let w = self.warmth();
+        if w > 0.2 {
+            Warmth::Warm
+        } else if w < -0.2 {
+            Warmth::Cool
+        } else {
+            Warmth::Neutral
+        }
+    }
+    /// CIE 1931 xy chromaticity
+    fn xy(&self) -> (f64, f64) {
+        let (x, y, z) = self.to_xyz();
+        let sum = x + y + z;
+        (x / sum, y / sum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn kelvin() {
+        let warm = Color::from_kelvin(2000.0);
+        let cool = Color::from_kelvin(10000.0);
+        assert!(warm.red > warm.blue);
+        assert!(cool.blue > cool.red);
+        assert_eq!(Color::from_kelvin(500.0), Color::from_kelvin(1000.0));
+        assert_eq!(Color::from_kelvin(1000.0).blue, 0.0);
+    }
+    #[test]
+    fn cct() {
+        let warm = Color::from_kelvin(3000.0);
+        let cool = Color::from_kelvin(9000.0);
+        assert!(warm.cct() < cool.cct());
+        assert!((warm.cct() - 3000.0).abs() < 300.0);
+        assert!(Color::new(0.0, 0.0, 0.0, 1.0).cct().is_nan());
+        assert!(Color::from_rgb1(0.8, 1.0, 0.8).duv() > 0.0);
+        assert!(Color::from_rgb1(1.0, 0.8, 1.0).duv() < 0.0);
+    }
+    #[test]
+    fn warmth() {
+        let mut v = vec![Color::from("blue"), Color::from("orange"), Color::from("gray")];
+        v.sort_by(|a, b| a.warmth().partial_cmp(&b.warmth()).unwrap());
+        assert_eq!(v, vec![Color::from("blue"), Color::from("gray"), Color::from("orange")]);
+        assert!(v.iter().all(|c| c.warmth().abs() <= 1.0));
+        assert_eq!(Color::from("teal").warmth_class(), Warmth::Cool);
+    }
+}
@@ -0,0 +1,24 @@
+//! Conversion to termcolor colors, with the "termcolor" feature
+
+use termcolor::Color as TermColor;
+use Color;
+
+impl From<Color> for TermColor {
+    /// Truecolor termcolor color, alpha is ignored
+    ///
+    /// ```
+    /// # extern crate termcolor;
+    /// # extern crate tint;
+    /// # use tint::Color;
+    /// # use termcolor::ColorSpec;
+    /// # fn main() {
+    /// let mut spec = ColorSpec::new();
+    /// spec.set_fg(Some(Color::from("#ff8000").into()));
+    /// assert_eq!(spec.fg(), Some(&termcolor::Color::Rgb(255, 128, 0)));
+    /// # }
+    /// ```
+    fn from(c: Color) -> TermColor {
+        let (r, g, b) = c.to_rgb255();
+        TermColor::Rgb(r, g, b)
+    }
+}
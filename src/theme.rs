@@ -0,0 +1,135 @@
+//! Terminal color schemes
+//!
+//! https://github.com/chriskempson/base16
+
+use adjust::clip;
+use Color;
+
+/// Oklch hues of the accents base08 - base0F: red, orange, yellow,
+/// green, cyan, blue, magenta and brown
+static ACCENT_HUES: [f64; 8] = [25.0, 55.0, 95.0, 145.0, 195.0, 255.0, 325.0, 50.0];
+
+/// Minimum contrast ratios against base00 for the foregrounds,
+/// base03 (comments) and base05 (default text)
+const COMMENT_CONTRAST: f64 = 3.0;
+const TEXT_CONTRAST: f64 = 7.0;
+const ACCENT_CONTRAST: f64 = 4.5;
+
+/// Base16 color scheme
+///
+///   base00 - base07 run from the background to the brightest
+///   foreground, base08 - base0F are accents
+///
+/// ```
+/// # use tint::{Base16, Color};
+/// let theme = Base16::generate(&Color::from_hex("#1d2021"), None);
+/// let bg = theme.colors()[0];
+/// let fg = theme.colors()[5];
+/// assert!(fg.contrast_ratio(&bg) >= 7.0);
+/// assert_eq!(theme.ansi_colors().len(), 16);
+/// ```
+#[derive(Debug,Clone,PartialEq)]
+pub struct Base16 {
+    colors: [Color; 16],
+}
+
+impl Base16 {
+    /// Generate a scheme from a background color and an optional accent color
+    ///
+    ///   The background sets base00 and the light or dark polarity;
+    ///   the grays keep a hint of its hue.  The accent color sets the
+    ///   lightness and chroma of the accents and replaces the accent
+    ///   with the closest hue.  Foregrounds and accents are adjusted
+    ///   to reach contrast ratios of at least 3 (base03), 7 (base05)
+    ///   and 4.5 (accents) against the background where possible
+    pub fn generate(background: &Color, accent: Option<&Color>) -> Base16 {
+        let bg = background.with_alpha(1.0);
+        let (l0, c0, h0) = bg.to_oklch();
+        let dark = l0 < 0.6;
+        let l7 = if dark { 0.97 } else { 0.15 };
+        let gray_chroma = c0.min(0.03);
+
+        let mut colors = [bg; 16];
+        for (i, c) in colors.iter_mut().enumerate().take(8).skip(1) {
+            let t = i as f64 / 7.0;
+            // Spend more of the range on the first steps, as base16 themes do
+            let t = 1.0 - (1.0 - t).powf(1.6);
+            let l = l0 + (l7 - l0) * t;
+            *c = clip(Color::from_oklch(l, gray_chroma * (1.0 - t * 0.5), h0), 1.0);
+        }
+        for &(i, target) in [(3, COMMENT_CONTRAST), (5, TEXT_CONTRAST)].iter() {
+            if let Some(c) = colors[i].ensure_contrast(&bg, target) {
+                colors[i] = c;
+            }
+        }
+
+        let (al, ac, ah) = match accent {
+            Some(a) => a.to_oklch(),
+            None => (if dark { 0.72 } else { 0.55 }, 0.14, f64::NAN),
+        };
+        let closest = ACCENT_HUES.iter().take(7).enumerate()
+            .map(|(i, &h)| (i, hue_distance(h, ah)))
+            .fold((None, f64::INFINITY), |best, (i, d)| if d < best.1 { (Some(i), d) } else { best })
+            .0;
+        for (i, &h) in ACCENT_HUES.iter().enumerate() {
+            let (l, c, h) = if Some(i) == closest {
+                (al, ac, ah)
+            } else if i == 7 {
+                // Brown, a dark desaturated orange
+                (al * 0.8, ac * 0.6, h)
+            } else {
+                (al, ac, h)
+            };
+            let c = clip(Color::from_oklch(l, c, h), 1.0);
+            colors[8 + i] = c.ensure_contrast(&bg, ACCENT_CONTRAST).unwrap_or(c);
+        }
+        Base16 { colors }
+    }
+    /// Colors base00 - base0F
+    pub fn colors(&self) -> &[Color; 16] {
+        &self.colors
+    }
+    /// Standard ANSI 16 terminal colors, using the base16 shell mapping
+    ///
+    ///   black, red, green, yellow, blue, magenta, cyan, white and
+    ///   their bright versions
+    pub fn ansi_colors(&self) -> [Color; 16] {
+        let c = &self.colors;
+        [c[0x0], c[0x8], c[0xB], c[0xA], c[0xD], c[0xE], c[0xC], c[0x5],
+         c[0x3], c[0x8], c[0xB], c[0xA], c[0xD], c[0xE], c[0xC], c[0x7]]
+    }
+}
+
+/// Angle between two hues in degrees, NaN if either is NaN
+fn hue_distance(a: f64, b: f64) -> f64 {
+    let d = (a - b).rem_euclid(360.0);
+    d.min(360.0 - d)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn check(theme: &Base16) {
+        let c = theme.colors();
+        let bg = c[0];
+        assert!(c[3].contrast_ratio(&bg) >= COMMENT_CONTRAST);
+        assert!(c[5].contrast_ratio(&bg) >= TEXT_CONTRAST);
+        for accent in &c[8..] {
+            assert!(accent.contrast_ratio(&bg) >= ACCENT_CONTRAST);
+        }
+        for w in c[..8].windows(2) {
+            assert!(w[0].contrast_ratio(&bg) <= w[1].contrast_ratio(&bg) + 1e-9);
+        }
+    }
+    #[test]
+    fn dark_and_light() {
+        check(&Base16::generate(&Color::from_hex("#282828"), None));
+        check(&Base16::generate(&Color::from_hex("#fdf6e3"), None));
+        let seed = Color::from_hex("#268bd2");
+        let theme = Base16::generate(&Color::from_hex("#002b36"), Some(&seed));
+        check(&theme);
+        let blue = theme.colors()[0xD];
+        assert!(blue.delta_e2000(&seed) < 10.0);
+        assert_eq!(theme.ansi_colors()[4], blue);
+    }
+}
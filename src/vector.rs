@@ -0,0 +1,55 @@
+//! Conversions to and from math library vectors, with the "glam" and
+//! "nalgebra" features
+//!
+//! Components are red, green, blue and alpha, as stored in Color
+
+use Color;
+
+#[cfg(feature = "glam")]
+impl From<Color> for ::glam::Vec4 {
+    fn from(c: Color) -> ::glam::Vec4 {
+        ::glam::Vec4::new(c.red as f32, c.green as f32, c.blue as f32, c.alpha as f32)
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<::glam::Vec4> for Color {
+    fn from(v: ::glam::Vec4) -> Color {
+        Color::new(v.x as f64, v.y as f64, v.z as f64, v.w as f64)
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl From<Color> for ::nalgebra::Vector4<f32> {
+    fn from(c: Color) -> ::nalgebra::Vector4<f32> {
+        ::nalgebra::Vector4::new(c.red as f32, c.green as f32, c.blue as f32, c.alpha as f32)
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl From<::nalgebra::Vector4<f32>> for Color {
+    fn from(v: ::nalgebra::Vector4<f32>) -> Color {
+        Color::new(v.x as f64, v.y as f64, v.z as f64, v.w as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "glam")]
+    #[test]
+    fn glam() {
+        let c = Color::new(0.25, 0.5, 0.75, 1.0);
+        let v = ::glam::Vec4::from(c);
+        assert_eq!(v, ::glam::Vec4::new(0.25, 0.5, 0.75, 1.0));
+        assert_eq!(Color::from(v * 2.0 - ::glam::Vec4::ONE), Color::new(-0.5, 0.0, 0.5, 1.0));
+    }
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn nalgebra() {
+        let c = Color::new(0.25, 0.5, 0.75, 1.0);
+        let v = ::nalgebra::Vector4::<f32>::from(c);
+        assert_eq!(v, ::nalgebra::Vector4::new(0.25, 0.5, 0.75, 1.0));
+        assert_eq!(Color::from(v), c);
+    }
+}